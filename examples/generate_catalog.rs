@@ -0,0 +1,93 @@
+//! Regenerates `src/catalog/feeds.rs` from a JSON snapshot of Chainlink feeds.
+//!
+//! The snapshot lists the feeds of every network by chain id, in the format of the
+//! per-network files of the Chainlink reference data directory:
+//!
+//! ```json
+//! { "networks": [{ "chainId": 56, "feeds": [{ "name": "ETH / USD", "proxyAddress": "0x...", "decimals": 8, "heartbeat": 60 }] }] }
+//! ```
+//!
+//! Usage: `cargo run --example generate_catalog [snapshot] [output]`, which defaults to
+//! `src/catalog/snapshot.json` and `src/catalog/feeds.rs`.
+
+use serde::Deserialize;
+use std::{env, fmt::Write, fs};
+
+#[derive(Deserialize)]
+struct Snapshot {
+    networks: Vec<Network>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Network {
+    chain_id: u64,
+    feeds: Vec<SnapshotFeed>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SnapshotFeed {
+    name: String,
+    /// Feeds that are not read through a proxy have none
+    proxy_address: Option<String>,
+    decimals: u8,
+    heartbeat: u64,
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let snapshot_path = args
+        .next()
+        .unwrap_or_else(|| "src/catalog/snapshot.json".to_string());
+    let output_path = args
+        .next()
+        .unwrap_or_else(|| "src/catalog/feeds.rs".to_string());
+
+    let snapshot = fs::read_to_string(&snapshot_path).expect("Could not read the snapshot");
+    let snapshot: Snapshot = serde_json::from_str(&snapshot).expect("Invalid snapshot");
+
+    let mut feeds = Vec::new();
+    for network in snapshot.networks {
+        for feed in network.feeds {
+            let Some(address) = feed.proxy_address else {
+                continue;
+            };
+            // "ETH / USD" is catalogued as "ETH/USD"
+            let pair = feed.name.split('/').map(str::trim).collect::<Vec<_>>();
+            feeds.push((
+                network.chain_id,
+                pair.join("/"),
+                address,
+                feed.decimals,
+                feed.heartbeat,
+            ));
+        }
+    }
+    feeds.sort();
+    feeds.dedup_by(|a, b| a.0 == b.0 && a.1 == b.1);
+
+    let mut output = String::new();
+    output.push_str(
+        "// Generated from `snapshot.json` by `cargo run --example generate_catalog`.\n\n",
+    );
+    output.push_str("use super::CatalogFeed;\n\n");
+    output.push_str("/// Every catalogued feed, by chain id\n");
+    output.push_str("pub(super) const FEEDS: &[(u64, CatalogFeed)] = &[\n");
+    for (chain_id, pair, address, decimals, heartbeat) in &feeds {
+        // Laid out the way rustfmt would, so formatting the crate leaves the catalog as is
+        writeln!(output, "    (").unwrap();
+        writeln!(output, "        {},", chain_id).unwrap();
+        writeln!(output, "        CatalogFeed {{").unwrap();
+        writeln!(output, "            pair: {:?},", pair).unwrap();
+        writeln!(output, "            address: {:?},", address).unwrap();
+        writeln!(output, "            decimals: {},", decimals).unwrap();
+        writeln!(output, "            heartbeat_seconds: {},", heartbeat).unwrap();
+        writeln!(output, "        }},").unwrap();
+        writeln!(output, "    ),").unwrap();
+    }
+    output.push_str("];\n");
+
+    fs::write(&output_path, output).expect("Could not write the catalog");
+    println!("Wrote {} feeds to {}", feeds.len(), output_path);
+}
@@ -0,0 +1,181 @@
+use std::time::Duration;
+
+#[cfg(feature = "catalog")]
+use crate::catalog::{self, Chain};
+use crate::core::{Broadcast, Reflector, Rustlink};
+use crate::error::Error;
+
+/// The fetch interval of instances built without one
+pub const DEFAULT_FETCH_INTERVAL_SECONDS: u64 = 10;
+
+/// The call timeout of instances built without one
+pub const DEFAULT_CALL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Collects the settings of a `Rustlink` instance one at a time, see `Rustlink::builder`.
+///
+/// Unlike `Rustlink::try_new`, every setting but the RPC url has a default, and invalid
+/// settings are only reported by `build`.
+pub struct RustlinkBuilder {
+    rpc_url: Option<String>,
+    fetch_interval_seconds: u64,
+    reflector: Option<Reflector>,
+    contracts: Vec<(String, String)>,
+    heartbeats: Vec<(String, u64)>,
+    call_timeout: Duration,
+    /// The first invalid setting, reported by `build`
+    error: Option<Error>,
+}
+
+impl Default for RustlinkBuilder {
+    fn default() -> Self {
+        RustlinkBuilder {
+            rpc_url: None,
+            fetch_interval_seconds: DEFAULT_FETCH_INTERVAL_SECONDS,
+            reflector: None,
+            contracts: Vec::new(),
+            heartbeats: Vec::new(),
+            call_timeout: DEFAULT_CALL_TIMEOUT,
+            error: None,
+        }
+    }
+}
+
+impl RustlinkBuilder {
+    /// The RPC url of the EVM network the contracts are deployed on
+    pub fn rpc_url(mut self, rpc_url: &str) -> Self {
+        self.rpc_url = Some(rpc_url.to_string());
+        self
+    }
+
+    /// How often to update data points, `DEFAULT_FETCH_INTERVAL_SECONDS` by default
+    pub fn fetch_interval(mut self, fetch_interval_seconds: u64) -> Self {
+        self.fetch_interval_seconds = fetch_interval_seconds;
+        self
+    }
+
+    /// How events are passed on. By default they are only passed on to `Rustlink::subscribe_events`.
+    pub fn reflector(mut self, reflector: Reflector) -> Self {
+        self.reflector = Some(reflector);
+        self
+    }
+
+    /// How long to wait for a call to a contract, `DEFAULT_CALL_TIMEOUT` by default
+    pub fn call_timeout(mut self, call_timeout: Duration) -> Self {
+        self.call_timeout = call_timeout;
+        self
+    }
+
+    /// Adds a contract by its address or ENS name, see `Rustlink::try_new`
+    pub fn contract(mut self, identifier: &str, address: &str) -> Self {
+        self.contracts
+            .push((identifier.to_string(), address.to_string()));
+        self
+    }
+
+    /// Adds the feed of a pair such as `ETH/USD` from the catalog of known feeds, together
+    /// with its heartbeat. The contract is identified by the pair as it is catalogued.
+    ///
+    /// ```rust
+    /// use rustlink::core::{Chain, Rustlink};
+    ///
+    /// let rustlink = Rustlink::builder()
+    ///     .rpc_url("https://bsc-dataseed1.binance.org/")
+    ///     .feed(Chain::Bsc, "ETH/USD")
+    ///     .feed(Chain::Bsc, "BTC/USD")
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// `build` returns `Error::NotInCatalog` if the pair is not catalogued for `chain`.
+    #[cfg(feature = "catalog")]
+    pub fn feed(mut self, chain: Chain, pair: &str) -> Self {
+        match catalog::find(chain, pair) {
+            Some(feed) => {
+                self.contracts
+                    .push((feed.pair.to_string(), feed.address.to_string()));
+                self.heartbeats
+                    .push((feed.pair.to_string(), feed.heartbeat_seconds));
+            }
+            None => {
+                self.error.get_or_insert(Error::NotInCatalog {
+                    chain: chain.to_string(),
+                    pair: pair.to_string(),
+                });
+            }
+        }
+        self
+    }
+
+    /// Creates the instance, see `Rustlink::try_new` for the errors it returns.
+    ///
+    /// Returns `Error::InvalidRpcUrl` if no RPC url was given as well.
+    pub fn build(self) -> Result<Rustlink, Error> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let Some(rpc_url) = self.rpc_url else {
+            return Err(Error::InvalidRpcUrl {
+                url: String::new(),
+                reason: "No RPC url was given".to_string(),
+            });
+        };
+        let reflector = self
+            .reflector
+            .unwrap_or_else(|| Reflector::Broadcast(Broadcast::new()));
+
+        let mut rustlink = Rustlink::try_new(
+            &rpc_url,
+            self.fetch_interval_seconds,
+            reflector,
+            self.contracts,
+            self.call_timeout,
+        )?;
+        for (identifier, heartbeat_seconds) in self.heartbeats {
+            rustlink = rustlink.with_heartbeat(&identifier, heartbeat_seconds)?;
+        }
+        Ok(rustlink)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::core::{Error, Rustlink};
+
+    #[test]
+    fn builder_applies_defaults() {
+        let rustlink = Rustlink::builder()
+            .rpc_url("https://bsc-dataseed1.binance.org/")
+            .contract("ETH", "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e")
+            .build()
+            .unwrap();
+        assert_eq!(rustlink.configuration.fetch_interval_seconds, 10);
+        assert_eq!(rustlink.configuration.contracts[0].identifier, "ETH");
+
+        assert!(matches!(
+            Rustlink::builder().build(),
+            Err(Error::InvalidRpcUrl { .. })
+        ));
+    }
+
+    #[cfg(feature = "catalog")]
+    #[test]
+    fn catalog_feeds_added_with_heartbeat() {
+        use crate::core::Chain;
+
+        let rustlink = Rustlink::builder()
+            .rpc_url("https://bsc-dataseed1.binance.org/")
+            .feed(Chain::Bsc, "eth/usd")
+            .build()
+            .unwrap();
+        let feed = &rustlink.configuration.contracts[0];
+        assert_eq!(feed.identifier, "ETH/USD");
+        assert_eq!(feed.heartbeat_seconds, Some(60));
+
+        let unknown = Rustlink::builder()
+            .rpc_url("https://bsc-dataseed1.binance.org/")
+            .feed(Chain::Bsc, "DOGE/EUR")
+            .build();
+        assert!(matches!(unknown, Err(Error::NotInCatalog { .. })));
+    }
+}
@@ -0,0 +1,160 @@
+// Generated from `snapshot.json` by `cargo run --example generate_catalog`.
+
+use super::CatalogFeed;
+
+/// Every catalogued feed, by chain id
+pub(super) const FEEDS: &[(u64, CatalogFeed)] = &[
+    (
+        1,
+        CatalogFeed {
+            pair: "BTC/USD",
+            address: "0xF4030086522a5bEEa4988F8cA5B36dbC97BeE88c",
+            decimals: 8,
+            heartbeat_seconds: 3600,
+        },
+    ),
+    (
+        1,
+        CatalogFeed {
+            pair: "ETH/USD",
+            address: "0x5f4eC3Df9cbd43714FE2740f5E3616155c5b8419",
+            decimals: 8,
+            heartbeat_seconds: 3600,
+        },
+    ),
+    (
+        1,
+        CatalogFeed {
+            pair: "LINK/USD",
+            address: "0x2c1d072e956AFFC0D435Cb7AC38EF18d24d9127c",
+            decimals: 8,
+            heartbeat_seconds: 3600,
+        },
+    ),
+    (
+        1,
+        CatalogFeed {
+            pair: "USDC/USD",
+            address: "0x8fFfFfd4AfB6115b954Bd326cbe7B4BA576818f6",
+            decimals: 8,
+            heartbeat_seconds: 86400,
+        },
+    ),
+    (
+        10,
+        CatalogFeed {
+            pair: "ETH/USD",
+            address: "0x13e3Ee699D1909E989722E753853AE30b17e08c5",
+            decimals: 8,
+            heartbeat_seconds: 1200,
+        },
+    ),
+    (
+        56,
+        CatalogFeed {
+            pair: "1INCH/USD",
+            address: "0x9a177Bb9f5b6083E962f9e62bD21d4b5660Aeb03",
+            decimals: 8,
+            heartbeat_seconds: 86400,
+        },
+    ),
+    (
+        56,
+        CatalogFeed {
+            pair: "BNB/USD",
+            address: "0x0567F2323251f0Aab15c8dFb1967E4e8A7D42aeE",
+            decimals: 8,
+            heartbeat_seconds: 60,
+        },
+    ),
+    (
+        56,
+        CatalogFeed {
+            pair: "BTC/USD",
+            address: "0x264990fbd0A4796A3E3d8E37C4d5F87a3aCa5Ebf",
+            decimals: 8,
+            heartbeat_seconds: 60,
+        },
+    ),
+    (
+        56,
+        CatalogFeed {
+            pair: "ETH/USD",
+            address: "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e",
+            decimals: 8,
+            heartbeat_seconds: 60,
+        },
+    ),
+    (
+        137,
+        CatalogFeed {
+            pair: "BTC/USD",
+            address: "0xc907E116054Ad103354f2D350FD2514433D57F6f",
+            decimals: 8,
+            heartbeat_seconds: 27,
+        },
+    ),
+    (
+        137,
+        CatalogFeed {
+            pair: "ETH/USD",
+            address: "0xF9680D99D6C9589e2a93a78A04A279e509205945",
+            decimals: 8,
+            heartbeat_seconds: 27,
+        },
+    ),
+    (
+        137,
+        CatalogFeed {
+            pair: "MATIC/USD",
+            address: "0xAB594600376Ec9fD91F8e885dADF0CE036862dE0",
+            decimals: 8,
+            heartbeat_seconds: 27,
+        },
+    ),
+    (
+        8453,
+        CatalogFeed {
+            pair: "ETH/USD",
+            address: "0x71041dddad3595F9CEd3DcCFBe3D1F4b0a16Bb70",
+            decimals: 8,
+            heartbeat_seconds: 1200,
+        },
+    ),
+    (
+        42161,
+        CatalogFeed {
+            pair: "BTC/USD",
+            address: "0x6ce185860a4963106506C203335A2910413708e9",
+            decimals: 8,
+            heartbeat_seconds: 86400,
+        },
+    ),
+    (
+        42161,
+        CatalogFeed {
+            pair: "ETH/USD",
+            address: "0x639Fe6ab55C921f74e7fac1ee960C0B6293ba612",
+            decimals: 8,
+            heartbeat_seconds: 86400,
+        },
+    ),
+    (
+        43114,
+        CatalogFeed {
+            pair: "AVAX/USD",
+            address: "0x0A77230d17318075983913bC2145DB16C7366156",
+            decimals: 8,
+            heartbeat_seconds: 86400,
+        },
+    ),
+    (
+        43114,
+        CatalogFeed {
+            pair: "ETH/USD",
+            address: "0x976B3D034E162d8bD72D6b9C989d545b839003b0",
+            decimals: 8,
+            heartbeat_seconds: 86400,
+        },
+    ),
+];
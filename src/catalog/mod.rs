@@ -0,0 +1,90 @@
+use std::fmt;
+
+mod feeds;
+
+/// A network on which Chainlink publishes feeds
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Chain {
+    Ethereum,
+    Optimism,
+    Bsc,
+    Polygon,
+    Base,
+    Arbitrum,
+    Avalanche,
+}
+
+impl Chain {
+    /// The EIP-155 chain id of the network
+    pub fn chain_id(self) -> u64 {
+        match self {
+            Chain::Ethereum => 1,
+            Chain::Optimism => 10,
+            Chain::Bsc => 56,
+            Chain::Polygon => 137,
+            Chain::Base => 8453,
+            Chain::Arbitrum => 42161,
+            Chain::Avalanche => 43114,
+        }
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// A feed as published by Chainlink at the time the catalog was generated
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CatalogFeed {
+    /// The pair the feed reports, e.g. `ETH/USD`
+    pub pair: &'static str,
+    /// The address of the proxy of the feed
+    pub address: &'static str,
+    /// Number of decimals of the answers
+    pub decimals: u8,
+    /// The maximum time in seconds between two updates of the feed
+    pub heartbeat_seconds: u64,
+}
+
+/// Finds the feed of a pair such as `ETH/USD` on the given network, ignoring case.
+///
+/// The catalog is generated from `snapshot.json` by `cargo run --example generate_catalog`.
+pub fn find(chain: Chain, pair: &str) -> Option<&'static CatalogFeed> {
+    feeds(chain).find(|feed| feed.pair.eq_ignore_ascii_case(pair))
+}
+
+/// Every catalogued feed of the given network
+pub fn feeds(chain: Chain) -> impl Iterator<Item = &'static CatalogFeed> {
+    feeds::FEEDS
+        .iter()
+        .filter(move |(chain_id, _)| *chain_id == chain.chain_id())
+        .map(|(_, feed)| feed)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use ethers::types::Address;
+    use std::str::FromStr;
+
+    use crate::catalog::{feeds, find, Chain};
+
+    #[test]
+    fn feeds_found_by_chain_and_pair() {
+        let eth = find(Chain::Bsc, "eth/usd").unwrap();
+        assert_eq!(eth.pair, "ETH/USD");
+        assert_eq!(eth.address, "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e");
+        assert_eq!(eth.decimals, 8);
+        assert_ne!(
+            find(Chain::Ethereum, "ETH/USD").unwrap().address,
+            eth.address
+        );
+        assert!(find(Chain::Bsc, "DOGE/EUR").is_none());
+
+        for chain in [Chain::Ethereum, Chain::Bsc, Chain::Arbitrum] {
+            assert!(feeds(chain).all(|feed| Address::from_str(feed.address).is_ok()));
+        }
+    }
+}
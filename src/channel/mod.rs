@@ -0,0 +1,230 @@
+use async_std::channel::{bounded, unbounded, Receiver, Sender, TrySendError};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use crate::interface::Round;
+
+/// A channel where every subscriber receives every value.
+/// Clones share the same subscribers.
+#[derive(Clone)]
+pub struct Broadcast<T> {
+    subscribers: Arc<Mutex<Vec<Sender<T>>>>,
+}
+
+impl<T> Default for Broadcast<T> {
+    fn default() -> Self {
+        Broadcast {
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl<T: Clone> Broadcast<T> {
+    /// Creates a broadcast channel without any subscribers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a subscriber that receives every value sent from now on.
+    /// Dropping the receiver unsubscribes it.
+    pub fn subscribe(&self) -> Receiver<T> {
+        let (sender, receiver) = unbounded();
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    /// Sends a value to every subscriber, dropping the ones that have unsubscribed
+    pub fn send(&self, value: T) {
+        self.subscribers.lock().unwrap().retain(|subscriber| {
+            !matches!(
+                subscriber.try_send(value.clone()),
+                Err(TrySendError::Closed(_))
+            )
+        });
+    }
+
+    /// Number of active subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.lock().unwrap().len()
+    }
+}
+
+/// Selects which feeds a subscription receives events for
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Filter {
+    /// Events of every feed
+    All,
+    /// Only events of the feeds with these identifiers
+    Identifiers(HashSet<String>),
+}
+
+impl Filter {
+    /// Only events of the feeds with the given identifiers
+    pub fn identifiers<I, S>(identifiers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Filter::Identifiers(identifiers.into_iter().map(Into::into).collect())
+    }
+
+    /// Whether the events of the feed with the given identifier pass this filter
+    pub fn matches(&self, identifier: &str) -> bool {
+        match self {
+            Filter::All => true,
+            Filter::Identifiers(identifiers) => identifiers.contains(identifier),
+        }
+    }
+}
+
+#[derive(Default)]
+struct WatchState {
+    /// Incremented for every update
+    version: u64,
+    /// The latest round of every feed with the version it was updated in
+    rounds: HashMap<String, (u64, Round)>,
+    /// Wakes up the receivers, holds at most one pending notification each
+    notifications: Vec<Sender<()>>,
+}
+
+/// A channel that only retains the latest round of every feed, by identifier.
+///
+/// Receivers never fall behind: a slow receiver skips the rounds that were replaced
+/// before it got to them. Clones share the same state.
+#[derive(Clone, Default)]
+pub struct Watch {
+    state: Arc<Mutex<WatchState>>,
+}
+
+impl Watch {
+    /// Creates a watch channel without any rounds
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a receiver that is notified about the rounds updated from now on
+    pub fn subscribe(&self) -> WatchReceiver {
+        let (sender, notification) = bounded(1);
+        let mut state = self.state.lock().unwrap();
+        state.notifications.push(sender);
+        WatchReceiver {
+            state: self.state.clone(),
+            notification,
+            seen_version: state.version,
+        }
+    }
+
+    /// Replaces the latest round of its feed and notifies every receiver
+    pub fn send(&self, round: Round) {
+        let mut state = self.state.lock().unwrap();
+        state.version += 1;
+        let version = state.version;
+        state
+            .rounds
+            .insert(round.identifier.clone(), (version, round));
+        // A full channel means the receiver has not picked up its previous notification yet.
+        state.notifications.retain(|notification| {
+            !matches!(notification.try_send(()), Err(TrySendError::Closed(_)))
+        });
+    }
+
+    /// The latest round of a feed
+    pub fn latest(&self, identifier: &str) -> Option<Round> {
+        let state = self.state.lock().unwrap();
+        state.rounds.get(identifier).map(|(_, round)| round.clone())
+    }
+}
+
+/// Receives the latest rounds of a `Watch` channel
+pub struct WatchReceiver {
+    state: Arc<Mutex<WatchState>>,
+    notification: Receiver<()>,
+    /// The last version this receiver has returned rounds for
+    seen_version: u64,
+}
+
+impl WatchReceiver {
+    /// Waits until a feed has a round that this receiver has not seen yet, then returns
+    /// the latest round of every feed that was updated since the last call.
+    pub async fn recv(&mut self) -> Vec<Round> {
+        loop {
+            {
+                let state = self.state.lock().unwrap();
+                let rounds: Vec<Round> = state
+                    .rounds
+                    .values()
+                    .filter(|(version, _)| *version > self.seen_version)
+                    .map(|(_, round)| round.clone())
+                    .collect();
+                if !rounds.is_empty() {
+                    self.seen_version = state.version;
+                    return rounds;
+                }
+            }
+            // The watch holds on to the sender, so the channel is never closed.
+            let _ = self.notification.recv().await;
+        }
+    }
+
+    /// The latest round of a feed, regardless of whether it was seen already
+    pub fn latest(&self, identifier: &str) -> Option<Round> {
+        let state = self.state.lock().unwrap();
+        state.rounds.get(identifier).map(|(_, round)| round.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::channel::{Broadcast, Filter, Watch};
+    use crate::test_utils::round;
+
+    #[tokio::test]
+    async fn every_subscriber_receives_every_round() {
+        let broadcast = Broadcast::new();
+        let first = broadcast.subscribe();
+        let second = broadcast.subscribe();
+
+        broadcast.send(round("ETH", 1));
+        broadcast.send(round("ETH", 2));
+        for receiver in [&first, &second] {
+            assert_eq!(receiver.recv().await.unwrap().round_id, 1);
+            assert_eq!(receiver.recv().await.unwrap().round_id, 2);
+        }
+
+        drop(second);
+        broadcast.send(round("ETH", 3));
+        assert_eq!(broadcast.subscriber_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn watch_retains_latest_round() {
+        let watch = Watch::new();
+        let mut receiver = watch.subscribe();
+
+        watch.send(round("ETH", 1));
+        watch.send(round("ETH", 2));
+        watch.send(round("BTC", 7));
+
+        let mut rounds = receiver.recv().await;
+        rounds.sort_by_key(|round| round.round_id);
+        let round_ids: Vec<u128> = rounds.iter().map(|round| round.round_id).collect();
+        assert_eq!(round_ids, vec![2, 7]);
+
+        watch.send(round("ETH", 3));
+        let rounds = receiver.recv().await;
+        assert_eq!(rounds.len(), 1);
+        assert_eq!(rounds[0].round_id, 3);
+        assert_eq!(receiver.latest("BTC").unwrap().round_id, 7);
+    }
+
+    #[test]
+    fn filter_by_identifier() {
+        let filter = Filter::identifiers(["ETH", "BTC"]);
+        assert!(filter.matches("ETH"));
+        assert!(!filter.matches("1INCH"));
+        assert!(Filter::All.matches("1INCH"));
+    }
+}
@@ -1,13 +1,35 @@
-use crate::{error::Error, fetcher::fetch_rounds, interface};
+use crate::{
+    builder, channel,
+    emission::{self, Emissions},
+    ens::{self, feed_address},
+    error, event, failover,
+    fetcher::{cached_contract, fetch_historical_round_data_for_contract, fetch_rounds, LogCursor},
+    history, interface, price, quorum,
+    registry::{self, FeedRegistry},
+    retry,
+    store::Store,
+};
 
-use async_std::channel::{unbounded, Receiver, RecvError, Sender};
-use ethers::{
-    providers::{Http, Provider},
-    types::Address,
+use async_std::{
+    channel::{unbounded, Receiver, Sender},
+    sync::RwLock,
+};
+use ethers::{providers::Provider, types::Address};
+use futures::{
+    future::{self, BoxFuture},
+    Future, FutureExt, Stream, StreamExt,
 };
 use js_sys::Function;
+use serde::Serialize;
 use serde_wasm_bindgen::{from_value, to_value};
-use std::str::FromStr;
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
 use wasm_bindgen_futures::spawn_local;
 use workflow_rs::core::cfg_if;
@@ -15,16 +37,93 @@ use workflow_rs::core::cfg_if;
 /// ## Configuration
 /// This struct contains the configuration for Rustlink. It contains the following fields:
 /// - `fetch_interval_seconds`: How often to update data points (to prevent RPC rate limitation)
-/// - `contracts`: A list of tuples containing a ticker name and its corresponding contract address on the EVM chain
-/// - `provider`: The provider to use for fetching data
+/// - `contracts`: A list of feeds containing a ticker name, its corresponding contract address on the EVM chain
+///   and feed specific settings
+/// - `provider`: The provider to use for fetching data, failing over between its RPC endpoints
+/// - `multicall`: The Multicall3 contract to batch all calls of a cycle with, if any
+/// - `max_concurrent_fetches`: How many contracts may be fetched at the same time
+/// - `retry`: How failing `decimals`, `latestRoundData` and batched calls are retried
+/// - `quorum`: The independent providers that have to agree on every round, if any
+/// - `websocket`: The websocket url to follow the logs of the feeds through instead of polling, if any
+/// - `event_logs`: Whether rounds are read from the logs of the aggregators instead of `latestRoundData`
 #[derive(Clone)]
 pub struct Configuration {
     pub fetch_interval_seconds: u64,
-    pub contracts: Vec<(String, Address)>,
-    pub provider: Provider<Http>,
+    pub contracts: Vec<Feed>,
+    pub provider: Provider<Failover>,
     pub call_timeout: std::time::Duration,
+    pub multicall: Option<Address>,
+    pub max_concurrent_fetches: usize,
+    pub retry: RetryPolicy,
+    pub quorum: Option<Quorum>,
+    pub websocket: Option<String>,
+    pub event_logs: bool,
+}
+
+/// ## Feed
+/// A single contract that Rustlink retrieves data from. It contains the following fields:
+/// - `identifier`: The ticker name of the underlying asset
+/// - `address`: The contract address on the EVM chain, zero if it is resolved from `ens_name`
+///   or if the feed is read through `registry`
+/// - `ens_name`: The ENS name the contract address is resolved from, if any
+/// - `registry`: The Feed Registry pair the feed is read through instead of `address`, if any
+/// - `fetch_interval_seconds`: How often to update this feed, overriding the global interval if set
+/// - `heartbeat_seconds`: The maximum age of a round before it is considered stale, if any
+/// - `emission_policy`: Which fetched rounds are passed on to the reflector
+#[derive(Clone, Debug)]
+pub struct Feed {
+    pub identifier: String,
+    pub address: Address,
+    pub ens_name: Option<String>,
+    pub registry: Option<RegistryPair>,
+    pub fetch_interval_seconds: Option<u64>,
+    pub heartbeat_seconds: Option<u64>,
+    pub emission_policy: EmissionPolicy,
+}
+
+impl Feed {
+    /// How often this feed is updated, falling back to the global interval of the configuration
+    pub fn fetch_interval_seconds(&self, configuration: &Configuration) -> u64 {
+        self.fetch_interval_seconds
+            .unwrap_or(configuration.fetch_interval_seconds)
+    }
+}
+
+/// ## ContractEntry
+/// A single entry of the `contracts` list of `Rustlink::try_new`, usually given as a tuple:
+/// - `(identifier, address)`: The contract is updated at the global fetch interval
+/// - `(identifier, address, fetch_interval_seconds)`: The contract is updated at its own interval
+///   if one is given, and at the global one otherwise
+#[derive(Clone, Debug)]
+pub struct ContractEntry {
+    pub identifier: String,
+    pub address: String,
+    pub fetch_interval_seconds: Option<u64>,
+}
+
+impl From<(String, String)> for ContractEntry {
+    fn from((identifier, address): (String, String)) -> Self {
+        ContractEntry {
+            identifier,
+            address,
+            fetch_interval_seconds: None,
+        }
+    }
+}
+
+impl From<(String, String, Option<u64>)> for ContractEntry {
+    fn from((identifier, address, fetch_interval_seconds): (String, String, Option<u64>)) -> Self {
+        ContractEntry {
+            identifier,
+            address,
+            fetch_interval_seconds,
+        }
+    }
 }
 
+/// The default number of contracts that are fetched at the same time
+pub const DEFAULT_MAX_CONCURRENT_FETCHES: usize = 10;
+
 /// ## Rustlink instance. This is the main struct that you will interact with.
 ///
 /// Rustlink is a lightweight Rust library that provides your Rust applications with a direct
@@ -39,12 +138,32 @@ pub struct Rustlink {
     pub termination_recv: Receiver<()>,
     pub shutdown_send: Sender<()>,
     pub shutdown_recv: Receiver<()>,
+    /// Metadata of every feed by identifier, loaded when the instance starts
+    pub(crate) feed_metadata: Arc<RwLock<HashMap<String, FeedMetadata>>>,
+    /// The latest round of every feed, updated by the fetcher
+    pub(crate) store: Store,
+    /// Where to send stale feed events to, if anywhere
+    pub(crate) stale_sender: Option<Sender<StaleFeed>>,
+    /// The last emitted round of every feed
+    pub(crate) emissions: Emissions,
+    /// Every emitted event, for the streams returned by `subscribe`
+    pub(crate) subscriptions: Broadcast,
+    /// Whether the instance has been started and not stopped since
+    pub(crate) running: Arc<AtomicBool>,
+    /// How far the logs of every feed have been read, when reading rounds from logs
+    pub(crate) log_cursors: Arc<RwLock<HashMap<String, LogCursor>>>,
+    /// The addresses the ENS names of feeds last resolved to, by identifier
+    pub(crate) ens_addresses: Arc<RwLock<HashMap<String, Address>>>,
 }
 
 /// Rustlink allows you as a developer to retrieve the sought Chainlink data in
 /// different ways.
 ///
-/// In its current state you can pass a Sender from an unbound async-std channel
+/// Every fetch of a contract is reported as an `Event`: either `Event::Round` with the
+/// fetched round, or `Event::Failed` with the identifier of the contract and a `FetchError`
+/// telling whether the call timed out, could not be decoded, reverted or did not reach the RPC.
+///
+/// You can pass a Sender from an unbound async-std channel
 /// which you can create by doing:
 /// ```rust
 /// use async_std::channel::unbounded;
@@ -55,17 +174,175 @@ pub struct Rustlink {
 /// let reflector=Reflector::Sender(sender);
 /// ```
 ///
-/// You may clone the receiver as many times as you want but do not use the sender
-/// for anything other than passing it to the try_new() method.
+/// Do not use the sender for anything other than passing it to the try_new() method.
+/// Clones of the receiver share the same channel, so every event is only received by one
+/// of them. Use `Reflector::Broadcast` if several subscribers should each receive every event:
+/// ```rust
+/// use rustlink::core::{Broadcast, Reflector};
+///
+/// let broadcast = Broadcast::new();
+/// let first_receiver = broadcast.subscribe();
+/// let second_receiver = broadcast.subscribe();
+///
+/// let reflector = Reflector::Broadcast(broadcast);
+/// ```
+///
+/// Slow consumers that are only interested in the latest round of every feed can use
+/// `Reflector::Watch`, which replaces rounds that have not been received yet. It does not
+/// retain failures, use `Rustlink::subscribe_events` alongside it to follow those:
+/// ```rust
+/// use rustlink::core::{Reflector, Watch};
+///
+/// let watch = Watch::new();
+/// let mut receiver = watch.subscribe();
+///
+/// let reflector = Reflector::Watch(watch);
+/// ```
+///
+/// Alternatively you can pass a closure that is called for every event, either a
+/// regular one or one that returns a future:
+/// ```rust
+/// use rustlink::core::{Event, Reflector};
+///
+/// let reflector = Reflector::callback(|event| println!("Received data: {:#?}", event));
+///
+/// let reflector = Reflector::async_callback(|event| async move {
+///     match event {
+///         Event::Round(round) => println!("Received data: {:#?}", round),
+///         Event::Failed { identifier, error, .. } => println!("{} failed: {}", identifier, error),
+///         Event::Disagreement { identifier, .. } => println!("Providers disagree on {}", identifier),
+///     }
+/// });
+/// ```
+///
+/// A panic inside a callback is caught and logged, and does not stop the fetching.
 #[derive(Clone)]
 pub enum Reflector {
     /// A sender from async-std
-    Sender(Sender<Round>),
+    Sender(Sender<Event>),
+    /// A closure that is called for every event
+    Callback(Callback),
+    /// A channel where every subscriber receives every event
+    Broadcast(Broadcast),
+    /// A channel that only retains the latest round of every feed, failures are not passed on
+    Watch(Watch),
+}
+
+pub type Broadcast = channel::Broadcast<Event>;
+
+pub type Watch = channel::Watch;
+
+pub type WatchReceiver = channel::WatchReceiver;
+
+pub type SubscriptionFilter = channel::Filter;
+
+/// A closure that is called for every event, returning a future that is awaited by the fetcher
+pub type Callback = Arc<dyn Fn(Event) -> BoxFuture<'static, ()> + Send + Sync>;
+
+impl Reflector {
+    /// Creates a reflector that calls `callback` for every event
+    pub fn callback<F>(callback: F) -> Self
+    where
+        F: Fn(Event) + Send + Sync + 'static,
+    {
+        Reflector::Callback(Arc::new(move |event| {
+            callback(event);
+            future::ready(()).boxed()
+        }))
+    }
+
+    /// Creates a reflector that calls `callback` for every event and awaits the returned future
+    pub fn async_callback<F, Fut>(callback: F) -> Self
+    where
+        F: Fn(Event) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Reflector::Callback(Arc::new(move |event| callback(event).boxed()))
+    }
 }
 
 pub type Round = interface::Round;
 
+pub type Error = error::Error;
+
+pub type Failover = failover::Failover;
+
+pub type EndpointHealth = failover::EndpointHealth;
+
+pub type Event = event::Event;
+
+pub type FetchError = event::FetchError;
+
+pub type EmissionPolicy = emission::EmissionPolicy;
+
+pub type RetryPolicy = retry::RetryPolicy;
+
+pub type Quorum = quorum::Quorum;
+
+/// Emitted whenever a round is fetched whose `updated_at` is older than the heartbeat
+/// of its feed. The feed has likely stopped updating and its answer should not be used.
+#[derive(Serialize, Debug, Clone)]
+pub struct StaleFeed {
+    /// Identifier of the stale feed
+    pub identifier: String,
+    /// The stale round
+    pub round: Round,
+    /// The heartbeat configured for the feed
+    pub heartbeat_seconds: u64,
+    /// Seconds that have passed since the round was updated
+    pub age_seconds: u64,
+}
+
+/// The address of the Multicall3 contract, which is deployed at
+/// the same address on all major EVM networks
+pub const MULTICALL3_ADDRESS: Address = ethers::contract::MULTICALL_ADDRESS;
+
+/// The address of the Chainlink Feed Registry, which is only deployed on Ethereum mainnet
+pub const FEED_REGISTRY_ADDRESS: Address = registry::FEED_REGISTRY_ADDRESS;
+
+/// How often the ENS names of feeds are resolved again
+pub const ENS_REFRESH_INTERVAL: std::time::Duration = ens::ENS_REFRESH_INTERVAL;
+
+pub type FeedMetadata = interface::FeedMetadata;
+
+pub type RegistryPair = registry::RegistryPair;
+
+pub type RustlinkBuilder = builder::RustlinkBuilder;
+
+#[cfg(feature = "catalog")]
+pub type Chain = crate::catalog::Chain;
+
+#[cfg(feature = "catalog")]
+pub type CatalogFeed = crate::catalog::CatalogFeed;
+
+pub type Price = price::Price;
+
+pub type ParsePriceError = price::ParsePriceError;
+
+pub type BackfillRange = history::BackfillRange;
+
+pub type RoundAt = history::RoundAt;
+
 impl Rustlink {
+    /// Starts building a Rustlink instance one setting at a time, as an alternative to `try_new`.
+    ///
+    /// ```rust
+    /// use rustlink::core::Rustlink;
+    ///
+    /// let rustlink = Rustlink::builder()
+    ///     .rpc_url("https://bsc-dataseed1.binance.org/")
+    ///     .fetch_interval(5)
+    ///     .contract("ETH", "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e")
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// With the `catalog` feature, known feeds can be added by network and pair with
+    /// `RustlinkBuilder::feed`, e.g. `.feed(Chain::Bsc, "ETH/USD")`.
+    pub fn builder() -> RustlinkBuilder {
+        RustlinkBuilder::default()
+    }
+
     /// Creates a new Rustlink instance.
     ///
     /// Expected parameters:
@@ -73,7 +350,13 @@ impl Rustlink {
     /// - `fetch_interval_seconds`: How often to update data points in the database (to prevent RPC rate limitation)
     /// - `reflector`: How you choose to receive the answer from your provided contracts.
     /// - `contracts`: A tuple list containing a ticker name and its corresponding contract address on the
-    ///   EVM chain.
+    ///   EVM chain. Instead of an address, an ENS name such as `eth-usd.data.eth` can be given, which is
+    ///   resolved through the provider when the instance starts and again every `ENS_REFRESH_INTERVAL`.
+    ///   A third `Option<u64>` field sets the fetch interval of the contract, see `ContractEntry`.
+    ///
+    /// Returns `Error::InvalidRpcUrl` or `Error::InvalidAddress` if the url or an address cannot be parsed,
+    /// `Error::DuplicateIdentifier` if two contracts share a ticker name and `Error::ZeroInterval` if
+    /// `fetch_interval_seconds` or the interval of a contract is `0`.
     ///
     /// Example:
     ///
@@ -101,30 +384,61 @@ impl Rustlink {
     ///     )
     ///     .unwrap();
     ///     rustlink.start();
-    ///     let round_data = receiver.recv().await.unwrap();
-    ///     println!("Received data: {:#?}", round_data);
+    ///     let event = receiver.recv().await.unwrap();
+    ///     println!("Received data: {:#?}", event);
     /// }
     /// ```
-    pub fn try_new(
+    pub fn try_new<C>(
         rpc_url: &str,
         fetch_interval_seconds: u64,
         reflector: Reflector,
-        contracts: Vec<(String, String)>,
+        contracts: Vec<C>,
         call_timeout: std::time::Duration,
-    ) -> Result<Self, Error> {
-        let provider = Provider::try_from(rpc_url).expect("Invalid RPC URL");
+    ) -> Result<Self, Error>
+    where
+        C: Into<ContractEntry>,
+    {
+        if fetch_interval_seconds == 0 {
+            return Err(Error::ZeroInterval);
+        }
+        let provider = Provider::new(Failover::new([rpc_url], call_timeout)?);
         let (termination_send, termination_recv) = unbounded::<()>();
         let (shutdown_send, shutdown_recv) = unbounded::<()>();
 
-        let parsed_contracts = contracts
-            .iter()
-            .map(|(identifier, address)| {
-                (
-                    identifier.clone(),
-                    Address::from_str(address).expect("Invalid contract address specified"),
-                )
-            })
-            .collect();
+        let mut identifiers = HashSet::new();
+        let mut parsed_contracts = Vec::with_capacity(contracts.len());
+        for contract in contracts {
+            let ContractEntry {
+                identifier,
+                address,
+                fetch_interval_seconds,
+            } = contract.into();
+            if !identifiers.insert(identifier.clone()) {
+                return Err(Error::DuplicateIdentifier(identifier));
+            }
+            if fetch_interval_seconds == Some(0) {
+                return Err(Error::ZeroInterval);
+            }
+            let (parsed_address, ens_name) = match Address::from_str(&address) {
+                Ok(parsed_address) => (parsed_address, None),
+                Err(_) if ens::is_ens_name(&address) => (Address::zero(), Some(address)),
+                Err(_) => {
+                    return Err(Error::InvalidAddress {
+                        identifier,
+                        address,
+                    })
+                }
+            };
+            parsed_contracts.push(Feed {
+                identifier,
+                address: parsed_address,
+                ens_name,
+                registry: None,
+                fetch_interval_seconds,
+                heartbeat_seconds: None,
+                emission_policy: EmissionPolicy::default(),
+            });
+        }
 
         Ok(Rustlink {
             configuration: Configuration {
@@ -132,18 +446,511 @@ impl Rustlink {
                 provider,
                 contracts: parsed_contracts,
                 call_timeout,
+                multicall: None,
+                max_concurrent_fetches: DEFAULT_MAX_CONCURRENT_FETCHES,
+                retry: RetryPolicy::default(),
+                quorum: None,
+                websocket: None,
+                event_logs: false,
             },
             reflector,
             termination_send,
             termination_recv,
             shutdown_send,
             shutdown_recv,
+            feed_metadata: Arc::new(RwLock::new(HashMap::new())),
+            store: Store::default(),
+            stale_sender: None,
+            emissions: Emissions::default(),
+            subscriptions: Broadcast::new(),
+            running: Arc::new(AtomicBool::new(false)),
+            log_cursors: Arc::new(RwLock::new(HashMap::new())),
+            ens_addresses: Arc::new(RwLock::new(HashMap::new())),
         })
     }
 
+    /// Batches the `latestRoundData` calls of all contracts into a single `aggregate3` call
+    /// to the Multicall3 contract at `address`, usually `MULTICALL3_ADDRESS`.
+    ///
+    /// Every contract is then refreshed once per fetch interval with a single RPC request.
+    /// Failing contracts do not affect the others.
+    ///
+    /// ```rust,no_run
+    /// use async_std::channel::unbounded;
+    /// use rustlink::core::{Reflector, Rustlink, MULTICALL3_ADDRESS};
+    ///
+    /// let contracts = vec![(
+    ///     "ETH".to_string(),
+    ///     "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e".to_string(),
+    /// )];
+    /// let (sender, _receiver) = unbounded();
+    /// let rustlink = Rustlink::try_new(
+    ///     "https://bsc-dataseed1.binance.org/",
+    ///     1,
+    ///     Reflector::Sender(sender),
+    ///     contracts,
+    ///     std::time::Duration::from_secs(10),
+    /// )
+    /// .unwrap()
+    /// .with_multicall(MULTICALL3_ADDRESS);
+    /// ```
+    pub fn with_multicall(mut self, address: Address) -> Self {
+        self.configuration.multicall = Some(address);
+        self
+    }
+
+    /// Reads every round from the `AnswerUpdated` and `NewRound` logs of the aggregators
+    /// instead of calling `latestRoundData`.
+    ///
+    /// Every fetch interval, the logs emitted since the last block that was read are requested
+    /// with `eth_getLogs`, so rounds that were reported between two fetches are reflected as well
+    /// instead of being skipped. The first fetch of a contract reads its latest round, and the
+    /// blocks after it are read from then on. Large gaps, e.g. after the RPC was unreachable
+    /// for a while, are caught up with over several fetches, and only the newest round that
+    /// was caught up on is checked against the heartbeat of the contract. Rounds are only read
+    /// through the provider of the instance, without batching them or reading them through a quorum.
+    ///
+    /// ```rust,no_run
+    /// use async_std::channel::unbounded;
+    /// use rustlink::core::{Reflector, Rustlink};
+    ///
+    /// let contracts = vec![(
+    ///     "ETH".to_string(),
+    ///     "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e".to_string(),
+    /// )];
+    /// let (sender, _receiver) = unbounded();
+    /// let rustlink = Rustlink::try_new(
+    ///     "https://bsc-dataseed1.binance.org/",
+    ///     1,
+    ///     Reflector::Sender(sender),
+    ///     contracts,
+    ///     std::time::Duration::from_secs(10),
+    /// )
+    /// .unwrap()
+    /// .with_event_logs();
+    /// ```
+    pub fn with_event_logs(mut self) -> Self {
+        self.configuration.event_logs = true;
+        self
+    }
+
+    /// Limits how many contracts are fetched at the same time, `DEFAULT_MAX_CONCURRENT_FETCHES`
+    /// by default.
+    ///
+    /// Every contract is refreshed once per fetch interval. Contracts that are due at the
+    /// same time are fetched concurrently, so a larger limit keeps them fresher at the cost
+    /// of more simultaneous requests to the RPC. A limit of `0` removes the limit.
+    pub fn with_max_concurrent_fetches(mut self, limit: usize) -> Self {
+        self.configuration.max_concurrent_fetches = limit;
+        self
+    }
+
+    /// Adds RPC endpoints to fail over to when the ones before them time out or fail,
+    /// in order of preference after the `rpc_url` the instance was created with.
+    ///
+    /// A failing endpoint is skipped for a cooldown that grows with every failure in a row, and
+    /// is preferred again as soon as a request to it succeeds after the cooldown, see `endpoint_health`
+    /// for the current state of every endpoint.
+    ///
+    /// Every endpoint is waited for up to the endpoint timeout before the next one is tried, however
+    /// many endpoints there are. It is the call timeout unless set with `with_endpoint_timeout`. The
+    /// call timeout still bounds a whole call, including its retries and the endpoints it fails over
+    /// to, so a hanging endpoint is only failed over from within the same call if the endpoint timeout
+    /// is shorter than the call timeout.
+    ///
+    /// ```rust
+    /// use async_std::channel::unbounded;
+    /// use rustlink::core::{Reflector, Rustlink};
+    ///
+    /// let contracts = vec![(
+    ///     "ETH".to_string(),
+    ///     "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e".to_string(),
+    /// )];
+    /// let (sender, _receiver) = unbounded();
+    /// let rustlink = Rustlink::try_new(
+    ///     "https://bsc-dataseed1.binance.org/",
+    ///     1,
+    ///     Reflector::Sender(sender),
+    ///     contracts,
+    ///     std::time::Duration::from_secs(10),
+    /// )
+    /// .unwrap()
+    /// .with_fallback_rpc_urls(["https://bsc-dataseed2.binance.org/", "https://bsc-rpc.publicnode.com/"])
+    /// .unwrap();
+    /// ```
+    ///
+    /// Returns `Error::InvalidRpcUrl` if an url cannot be parsed.
+    pub fn with_fallback_rpc_urls<I, S>(mut self, rpc_urls: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let failover = self.configuration.provider.as_ref();
+        let mut urls = failover.urls();
+        urls.extend(rpc_urls.into_iter().map(|url| url.as_ref().to_string()));
+        let request_timeout = failover.request_timeout();
+        self.configuration.provider = Provider::new(Failover::new(urls, request_timeout)?);
+        Ok(self)
+    }
+
+    /// Waits up to `endpoint_timeout` for a single RPC endpoint before failing over to the next one,
+    /// instead of the call timeout.
+    ///
+    /// With fallback RPC urls, an endpoint timeout shorter than the call timeout leaves the call time
+    /// to fail over and retry, e.g. an endpoint timeout of 2 seconds lets a call with a 10 second
+    /// timeout skip a hanging endpoint and still be retried, see `with_fallback_rpc_urls`.
+    pub fn with_endpoint_timeout(mut self, endpoint_timeout: std::time::Duration) -> Self {
+        let failover = self.configuration.provider.as_ref().clone();
+        self.configuration.provider =
+            Provider::new(failover.with_request_timeout(endpoint_timeout));
+        self
+    }
+
+    /// Follows the logs of every contract through the websocket at `ws_url` instead of polling
+    /// them every fetch interval.
+    ///
+    /// A contract is only refreshed when its proxy or the aggregator it points to emits a log, e.g.
+    /// when a new round is reported, which saves the calls that polling makes between updates.
+    /// Every contract is also refreshed whenever the websocket (re)connects. The `latestRoundData`
+    /// calls are still made through the RPC urls, and the rounds are reflected just like in polling mode.
+    /// As a feed that stopped reporting rounds emits no logs, the latest round of every feed is
+    /// checked for staleness at its heartbeat, see `with_heartbeat`.
+    ///
+    /// ```rust
+    /// use async_std::channel::unbounded;
+    /// use rustlink::core::{Reflector, Rustlink};
+    ///
+    /// let contracts = vec![(
+    ///     "ETH".to_string(),
+    ///     "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e".to_string(),
+    /// )];
+    /// let (sender, _receiver) = unbounded();
+    /// let rustlink = Rustlink::try_new(
+    ///     "https://bsc-dataseed1.binance.org/",
+    ///     1,
+    ///     Reflector::Sender(sender),
+    ///     contracts,
+    ///     std::time::Duration::from_secs(10),
+    /// )
+    /// .unwrap()
+    /// .with_websocket("wss://bsc-rpc.publicnode.com")
+    /// .unwrap();
+    /// ```
+    ///
+    /// Returns `Error::InvalidRpcUrl` if `ws_url` is not a `ws://` or `wss://` url.
+    pub fn with_websocket(mut self, ws_url: &str) -> Result<Self, Error> {
+        let invalid = |reason: String| Error::InvalidRpcUrl {
+            url: ws_url.to_string(),
+            reason,
+        };
+        let url = reqwest::Url::parse(ws_url).map_err(|error| invalid(error.to_string()))?;
+        if !matches!(url.scheme(), "ws" | "wss") {
+            return Err(invalid("Expected a ws:// or wss:// url".to_string()));
+        }
+        self.configuration.websocket = Some(ws_url.to_string());
+        Ok(self)
+    }
+
+    /// Adds a contract for every `(base, quote)` pair, such as `("ETH", "USD")`, that is read
+    /// through the Chainlink Feed Registry at `registry`, usually `FEED_REGISTRY_ADDRESS`. The
+    /// contracts are identified as `base/quote`, e.g. `ETH/USD`.
+    ///
+    /// The rounds, decimals and phase of a pair are read from the registry, which forwards them
+    /// to the aggregator it currently uses for the pair. The aggregator is resolved again through
+    /// `getFeed` whenever the registry moves the pair to a new phase. These contracts are neither
+    /// batched with multicall nor read from logs, and their history cannot be walked: `fetch_round`,
+    /// `backfill` and `round_at` return `Error::RegistryFeed` for them.
+    ///
+    /// Assets are given by their token address, or by their symbol if the registry denotes them
+    /// by a reserved address, which is the case for `ETH`, `BTC` and fiat currencies like `USD`.
+    /// The registry only exists on some networks, so the instance has to be connected to one.
+    ///
+    /// ```rust,no_run
+    /// use async_std::channel::unbounded;
+    /// use rustlink::core::{Reflector, Rustlink, FEED_REGISTRY_ADDRESS};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (sender, _receiver) = unbounded();
+    ///     let rustlink = Rustlink::try_new(
+    ///         "https://eth.llamarpc.com",
+    ///         1,
+    ///         Reflector::Sender(sender),
+    ///         Vec::<(String, String)>::new(),
+    ///         std::time::Duration::from_secs(10),
+    ///     )
+    ///     .unwrap()
+    ///     .with_registry_feeds(FEED_REGISTRY_ADDRESS, [("ETH", "USD"), ("BTC", "ETH")])
+    ///     .await
+    ///     .unwrap();
+    ///     rustlink.start();
+    /// }
+    /// ```
+    ///
+    /// Returns `Error::InvalidAddress` if an asset is unknown, `Error::DuplicateIdentifier` if
+    /// a pair is already configured and `Error::Fetch` if the registry has no feed for a pair
+    /// or cannot be reached.
+    pub async fn with_registry_feeds<I, B, Q>(
+        mut self,
+        registry: Address,
+        pairs: I,
+    ) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (B, Q)>,
+        B: AsRef<str>,
+        Q: AsRef<str>,
+    {
+        let feed_registry = FeedRegistry::new(
+            &self.configuration.provider,
+            registry,
+            self.configuration.call_timeout,
+        );
+        let mut feeds: Vec<Feed> = Vec::new();
+        for (base, quote) in pairs {
+            let (base, quote) = (base.as_ref(), quote.as_ref());
+            let identifier = format!("{}/{}", base, quote);
+            let mut identifiers = self.configuration.contracts.iter().chain(&feeds);
+            if identifiers.any(|feed| feed.identifier == identifier) {
+                return Err(Error::DuplicateIdentifier(identifier));
+            }
+            let (base, quote) = registry::pair_addresses(base, quote)?;
+            // The registry reverts if it has no feed for the pair.
+            feed_registry.feed(base, quote).await?;
+            feeds.push(Feed {
+                identifier,
+                address: Address::zero(),
+                ens_name: None,
+                registry: Some(RegistryPair {
+                    registry,
+                    base,
+                    quote,
+                }),
+                fetch_interval_seconds: None,
+                heartbeat_seconds: None,
+                emission_policy: EmissionPolicy::default(),
+            });
+        }
+        self.configuration.contracts.extend(feeds);
+        Ok(self)
+    }
+
+    /// Reads the latest round of every contract through the provider of this instance and one
+    /// independent provider per url in `rpc_urls`, and only emits a round once `threshold` of
+    /// them report the same round id and answer. This protects against a single lying or
+    /// lagging RPC node. The threshold has to be a majority of the providers, so two different
+    /// rounds can never both reach it.
+    ///
+    /// If the providers that responded do not reach the threshold, an `Event::Disagreement`
+    /// with their rounds is emitted instead. Contracts are not batched with multicall in this mode,
+    /// a multicall contract set with `with_multicall` is ignored with a warning.
+    ///
+    /// ```rust
+    /// use async_std::channel::unbounded;
+    /// use rustlink::core::{Reflector, Rustlink};
+    ///
+    /// let contracts = vec![(
+    ///     "ETH".to_string(),
+    ///     "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e".to_string(),
+    /// )];
+    /// let (sender, _receiver) = unbounded();
+    /// let rustlink = Rustlink::try_new(
+    ///     "https://bsc-dataseed1.binance.org/",
+    ///     1,
+    ///     Reflector::Sender(sender),
+    ///     contracts,
+    ///     std::time::Duration::from_secs(10),
+    /// )
+    /// .unwrap()
+    /// // Two of the three providers have to agree.
+    /// .with_quorum(["https://bsc-rpc.publicnode.com/", "https://binance.llamarpc.com/"], 2)
+    /// .unwrap();
+    /// ```
+    ///
+    /// Returns `Error::InvalidRpcUrl` if an url cannot be parsed, and `Error::InvalidQuorum` if
+    /// `rpc_urls` is empty, or `threshold` is not more than half of the providers or larger than
+    /// the number of providers.
+    pub fn with_quorum<I, S>(mut self, rpc_urls: I, threshold: usize) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut providers = vec![self.configuration.provider.clone()];
+        for rpc_url in rpc_urls {
+            let failover = Failover::new([rpc_url], self.configuration.call_timeout)?;
+            providers.push(Provider::new(failover));
+        }
+        // A single provider has nothing to agree with.
+        if providers.len() < 2 || threshold <= providers.len() / 2 || threshold > providers.len() {
+            return Err(Error::InvalidQuorum {
+                threshold,
+                providers: providers.len(),
+            });
+        }
+        self.configuration.quorum = Some(Quorum {
+            providers,
+            threshold,
+        });
+        Ok(self)
+    }
+
+    /// Retrieves the health of every RPC endpoint, in order of preference
+    pub fn endpoint_health(&self) -> Vec<EndpointHealth> {
+        self.configuration.provider.as_ref().health()
+    }
+
+    /// Retries failing `decimals`, `latestRoundData` and batched calls according to `retry`,
+    /// `RetryPolicy::default()` by default. Use `RetryPolicy::none()` to disable retries.
+    ///
+    /// Transient failures such as timeouts and RPC errors are retried with an exponential
+    /// backoff, while reverts are not. All attempts of a call together are bounded by the
+    /// call timeout, and the number of attempts is recorded on the `Round` or failure event.
+    ///
+    /// ```rust
+    /// use async_std::channel::unbounded;
+    /// use rustlink::core::{Reflector, RetryPolicy, Rustlink};
+    /// use std::time::Duration;
+    ///
+    /// let contracts = vec![(
+    ///     "ETH".to_string(),
+    ///     "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e".to_string(),
+    /// )];
+    /// let (sender, _receiver) = unbounded();
+    /// let rustlink = Rustlink::try_new(
+    ///     "https://bsc-dataseed1.binance.org/",
+    ///     1,
+    ///     Reflector::Sender(sender),
+    ///     contracts,
+    ///     Duration::from_secs(10),
+    /// )
+    /// .unwrap()
+    /// .with_retry_policy(RetryPolicy {
+    ///     max_attempts: 5,
+    ///     base_delay: Duration::from_millis(100),
+    ///     max_delay: Duration::from_secs(1),
+    ///     jitter: true,
+    /// });
+    /// ```
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.configuration.retry = retry;
+        self
+    }
+
+    /// Updates the contract with the given identifier every `fetch_interval_seconds` instead of
+    /// using the global fetch interval, like an interval given in the contracts list of `try_new`.
+    ///
+    /// Every contract is scheduled independently, so slowly moving feeds such as stablecoins
+    /// can be polled less often than volatile ones.
+    ///
+    /// Returns `Error::NotFound` if `identifier` is not among the configured contracts and
+    /// `Error::ZeroInterval` if `fetch_interval_seconds` is `0`.
+    pub fn with_fetch_interval(
+        mut self,
+        identifier: &str,
+        fetch_interval_seconds: u64,
+    ) -> Result<Self, Error> {
+        if fetch_interval_seconds == 0 {
+            return Err(Error::ZeroInterval);
+        }
+        self.find_contract_mut(identifier)?.fetch_interval_seconds = Some(fetch_interval_seconds);
+        Ok(self)
+    }
+
+    /// Declares the heartbeat of the contract with the given identifier, which is the
+    /// maximum time in seconds between two updates of the feed as published by Chainlink.
+    ///
+    /// Rounds whose `updated_at` is older than the heartbeat are marked as `stale`, and a
+    /// `StaleFeed` event is emitted for them if a stale sender is configured, see
+    /// `with_stale_sender`.
+    ///
+    /// Returns `Error::NotFound` if `identifier` is not among the configured contracts.
+    pub fn with_heartbeat(
+        mut self,
+        identifier: &str,
+        heartbeat_seconds: u64,
+    ) -> Result<Self, Error> {
+        self.find_contract_mut(identifier)?.heartbeat_seconds = Some(heartbeat_seconds);
+        Ok(self)
+    }
+
+    /// Sets which fetched rounds of the contract with the given identifier are passed on
+    /// to the reflector. By default every fetched round is passed on.
+    ///
+    /// The latest round in the store is updated regardless of the policy.
+    ///
+    /// ```rust
+    /// use async_std::channel::unbounded;
+    /// use rustlink::core::{EmissionPolicy, Reflector, Rustlink};
+    ///
+    /// let contracts = vec![(
+    ///     "ETH".to_string(),
+    ///     "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e".to_string(),
+    /// )];
+    /// let (sender, _receiver) = unbounded();
+    /// let rustlink = Rustlink::try_new(
+    ///     "https://bsc-dataseed1.binance.org/",
+    ///     1,
+    ///     Reflector::Sender(sender),
+    ///     contracts,
+    ///     std::time::Duration::from_secs(10),
+    /// )
+    /// .unwrap()
+    /// // Emit when ETH moves more than 0.5%, and at least once an hour.
+    /// .with_emission_policy(
+    ///     "ETH",
+    ///     EmissionPolicy::on_deviation_basis_points(50).force_after_seconds(3600),
+    /// )
+    /// .unwrap();
+    /// ```
+    ///
+    /// Returns `Error::NotFound` if `identifier` is not among the configured contracts.
+    pub fn with_emission_policy(
+        mut self,
+        identifier: &str,
+        emission_policy: EmissionPolicy,
+    ) -> Result<Self, Error> {
+        self.find_contract_mut(identifier)?.emission_policy = emission_policy;
+        Ok(self)
+    }
+
+    /// Sends a `StaleFeed` event to `sender` for every fetched round that is older than the
+    /// heartbeat of its feed, e.g. to halt trading on that asset.
+    ///
+    /// ```rust
+    /// use async_std::channel::unbounded;
+    /// use rustlink::core::{Reflector, Rustlink};
+    ///
+    /// let contracts = vec![(
+    ///     "ETH".to_string(),
+    ///     "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e".to_string(),
+    /// )];
+    /// let (sender, _receiver) = unbounded();
+    /// let (stale_sender, _stale_receiver) = unbounded();
+    /// let rustlink = Rustlink::try_new(
+    ///     "https://bsc-dataseed1.binance.org/",
+    ///     1,
+    ///     Reflector::Sender(sender),
+    ///     contracts,
+    ///     std::time::Duration::from_secs(10),
+    /// )
+    /// .unwrap()
+    /// .with_heartbeat("ETH", 86400)
+    /// .unwrap()
+    /// .with_stale_sender(stale_sender);
+    /// ```
+    pub fn with_stale_sender(mut self, sender: Sender<StaleFeed>) -> Self {
+        self.stale_sender = Some(sender);
+        self
+    }
+
     /// Starts the Rustlink instance.
     /// This method will start fetching the latest price data from the Chainlink decentralized data feed.
+    /// Starting an instance that is already running has no effect.
     pub fn start(&self) {
+        if self.running.swap(true, Ordering::SeqCst) {
+            log::warn!("Rustlink is already running");
+            return;
+        }
+
         #[cfg(not(target_arch = "wasm32"))]
         tokio::task::spawn(fetch_rounds(self.clone()));
 
@@ -153,9 +960,260 @@ impl Rustlink {
 
     /// Stops the Rustlink instance.
     /// This method will stop fetching the latest price data from the Chainlink decentralized data feed.
-    pub async fn stop(&self) -> Result<(), RecvError> {
-        self.termination_send.send(()).await.unwrap();
-        self.shutdown_recv.recv().await
+    ///
+    /// Returns `Error::NotRunning` if the instance has not been started or was already stopped.
+    pub async fn stop(&self) -> Result<(), Error> {
+        if !self.running.swap(false, Ordering::SeqCst) {
+            return Err(Error::NotRunning);
+        }
+        // Every instance holds both ends of these channels, so they are never closed.
+        let _ = self.termination_send.send(()).await;
+        let _ = self.shutdown_recv.recv().await;
+        Ok(())
+    }
+
+    /// Retrieves a past round for one of the configured contracts.
+    ///
+    /// This lets you look up the exact answer that was reported in a given round, e.g. to
+    /// audit which price was seen at settlement time. Use the `round_id` of a previously
+    /// received `Round` to query it again.
+    ///
+    /// Returns `Error::NotFound` if `identifier` is not among the configured contracts and
+    /// `Error::RegistryFeed` if the contract is read through a Feed Registry.
+    pub async fn fetch_round(&self, identifier: &str, round_id: u128) -> Result<Round, Error> {
+        let feed = self.find_proxy_contract(identifier)?;
+        let address = feed_address(self, feed).await?;
+
+        fetch_historical_round_data_for_contract(self, &feed.identifier, address, round_id)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Streams every historical round of one of the configured contracts within `range`.
+    ///
+    /// Rounds are yielded from the most recent to the oldest one. Chainlink proxies encode
+    /// the phase in the round id (see `Round::phase_id`), so the backfill walks backwards
+    /// through every phase of the proxy and skips rounds that are missing where a phase starts.
+    ///
+    /// Example:
+    ///
+    /// ```rust,no_run
+    /// use async_std::channel::unbounded;
+    /// use futures::StreamExt;
+    /// use rustlink::core::{BackfillRange, Reflector, Rustlink};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let contracts = vec![(
+    ///         "ETH".to_string(),
+    ///         "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e".to_string(),
+    ///     )];
+    ///     let (sender, _receiver) = unbounded();
+    ///     let rustlink = Rustlink::try_new(
+    ///         "https://bsc-dataseed1.binance.org/",
+    ///         1,
+    ///         Reflector::Sender(sender),
+    ///         contracts,
+    ///         std::time::Duration::from_secs(10),
+    ///     )
+    ///     .unwrap();
+    ///
+    ///     let range = BackfillRange::Timestamps {
+    ///         from: 1715000000,
+    ///         to: 1715086400,
+    ///     };
+    ///     let rounds = rustlink.backfill("ETH", range).await.unwrap();
+    ///     futures::pin_mut!(rounds);
+    ///     while let Some(round) = rounds.next().await {
+    ///         println!("Received data: {:#?}", round);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Returns `Error::NotFound` if `identifier` is not among the configured contracts and
+    /// `Error::RegistryFeed` if the contract is read through a Feed Registry.
+    pub async fn backfill(
+        &self,
+        identifier: &str,
+        range: BackfillRange,
+    ) -> Result<impl Stream<Item = Result<Round, Error>> + '_, Error> {
+        let feed = self.find_proxy_contract(identifier)?;
+        let address = feed_address(self, feed).await?;
+
+        let contract = cached_contract(self, &feed.identifier, address).await?;
+        let rounds = history::backfill(contract, range).await?;
+
+        Ok(rounds.map(|round| round.map_err(Error::from)))
+    }
+
+    /// Finds the round that was in effect at the given unix timestamp (in seconds) for
+    /// one of the configured contracts.
+    ///
+    /// Returns the latest round with `updated_at <= unix_ts` together with the round that
+    /// replaced it, so you can see for how long the answer was valid. The rounds are located
+    /// by binary searching every phase of the proxy, which only takes a handful of calls.
+    ///
+    /// Returns `Error::RoundNotFound` if the feed has no round at or before `unix_ts`, and
+    /// `Error::RegistryFeed` if the contract is read through a Feed Registry.
+    pub async fn round_at(&self, identifier: &str, unix_ts: u64) -> Result<RoundAt, Error> {
+        let feed = self.find_proxy_contract(identifier)?;
+        let address = feed_address(self, feed).await?;
+
+        let contract = cached_contract(self, &feed.identifier, address).await?;
+
+        history::round_at(&contract, unix_ts)
+            .await?
+            .ok_or(Error::RoundNotFound(unix_ts))
+    }
+
+    /// Retrieves the latest round of a `(base, quote)` pair straight from `latestRoundData(base, quote)`
+    /// of the Chainlink Feed Registry at `registry`, without configuring a contract for it.
+    /// The round is identified as `base/quote`, see `with_registry_feeds` for how assets are given.
+    ///
+    /// Returns `Error::InvalidAddress` if an asset is unknown and `Error::Fetch` if the registry
+    /// has no feed for the pair or cannot be reached.
+    pub async fn registry_round(
+        &self,
+        registry: Address,
+        base: &str,
+        quote: &str,
+    ) -> Result<Round, Error> {
+        let (base_address, quote_address) = registry::pair_addresses(base, quote)?;
+        let feed_registry = FeedRegistry::new(
+            &self.configuration.provider,
+            registry,
+            self.configuration.call_timeout,
+        );
+        let identifier = format!("{}/{}", base, quote);
+        Ok(feed_registry
+            .latest_round_data(&identifier, base_address, quote_address)
+            .await?)
+    }
+
+    /// Retrieves the cached metadata of one of the configured contracts.
+    ///
+    /// The metadata of every feed is loaded once when the instance starts and is only
+    /// refreshed when the proxy of the feed moves to a new aggregator. Returns `None`
+    /// if the metadata has not been loaded yet.
+    pub async fn metadata(&self, identifier: &str) -> Option<FeedMetadata> {
+        self.feed_metadata.read().await.get(identifier).cloned()
+    }
+
+    /// Subscribes to the rounds of the contracts selected by `filter`.
+    ///
+    /// The returned stream receives every round that is passed on to the reflector from now
+    /// on, regardless of which reflector is configured, so different parts of an application
+    /// can each follow their own feeds. Use `subscribe_events` to receive failures as well.
+    ///
+    /// ```rust,no_run
+    /// use async_std::channel::unbounded;
+    /// use futures::StreamExt;
+    /// use rustlink::core::{Reflector, Rustlink, SubscriptionFilter};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let contracts = vec![
+    ///         (
+    ///             "ETH".to_string(),
+    ///             "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e".to_string(),
+    ///         ),
+    ///         (
+    ///             "1INCH".to_string(),
+    ///             "0x9a177Bb9f5b6083E962f9e62bD21d4b5660Aeb03".to_string(),
+    ///         ),
+    ///     ];
+    ///     let (sender, _receiver) = unbounded();
+    ///     let rustlink = Rustlink::try_new(
+    ///         "https://bsc-dataseed1.binance.org/",
+    ///         1,
+    ///         Reflector::Sender(sender),
+    ///         contracts,
+    ///         std::time::Duration::from_secs(10),
+    ///     )
+    ///     .unwrap();
+    ///
+    ///     let rounds = rustlink.subscribe(SubscriptionFilter::identifiers(["ETH"]));
+    ///     futures::pin_mut!(rounds);
+    ///     rustlink.start();
+    ///     while let Some(round) = rounds.next().await {
+    ///         println!("Received data: {:#?}", round);
+    ///     }
+    /// }
+    /// ```
+    pub fn subscribe(
+        &self,
+        filter: SubscriptionFilter,
+    ) -> impl Stream<Item = Round> + Send + 'static {
+        self.subscribe_events(filter).filter_map(|event| {
+            future::ready(match event {
+                Event::Round(round) => Some(round),
+                _ => None,
+            })
+        })
+    }
+
+    /// Subscribes to every event of the contracts selected by `filter`, like `subscribe`,
+    /// including the failed fetches and the disagreements between providers.
+    pub fn subscribe_events(
+        &self,
+        filter: SubscriptionFilter,
+    ) -> impl Stream<Item = Event> + Send + 'static {
+        self.subscriptions
+            .subscribe()
+            .filter(move |event| future::ready(filter.matches(event.identifier())))
+    }
+
+    /// Retrieves the latest round received for one of the configured contracts.
+    ///
+    /// The latest round of every contract is kept in memory as soon as it is fetched,
+    /// regardless of the reflector. Returns `None` if no round has been received yet.
+    pub async fn latest(&self, identifier: &str) -> Option<Round> {
+        self.store.latest(identifier).await
+    }
+
+    /// Retrieves the latest round received for every contract, by identifier
+    pub async fn all_latest(&self) -> HashMap<String, Round> {
+        self.store.all_latest().await
+    }
+
+    /// Retrieves when the latest round of one of the configured contracts was received,
+    /// as a unix timestamp in milliseconds
+    pub async fn last_update(&self, identifier: &str) -> Option<u64> {
+        self.store.last_update(identifier).await
+    }
+
+    /// Retrieves when the latest round of every contract was received, by identifier,
+    /// as unix timestamps in milliseconds
+    pub async fn last_updates(&self) -> HashMap<String, u64> {
+        self.store.last_updates().await
+    }
+
+    /// Looks up a configured contract by its identifier
+    fn find_contract(&self, identifier: &str) -> Result<&Feed, Error> {
+        self.configuration
+            .contracts
+            .iter()
+            .find(|feed| feed.identifier == identifier)
+            .ok_or_else(|| Error::NotFound(identifier.to_string()))
+    }
+
+    /// Looks up a configured contract by its identifier, which has to be read through its own
+    /// proxy rather than a Feed Registry
+    fn find_proxy_contract(&self, identifier: &str) -> Result<&Feed, Error> {
+        let feed = self.find_contract(identifier)?;
+        if feed.registry.is_some() {
+            return Err(Error::RegistryFeed(identifier.to_string()));
+        }
+        Ok(feed)
+    }
+
+    /// Looks up a configured contract by its identifier for modification
+    fn find_contract_mut(&mut self, identifier: &str) -> Result<&mut Feed, Error> {
+        self.configuration
+            .contracts
+            .iter_mut()
+            .find(|feed| feed.identifier == identifier)
+            .ok_or_else(|| Error::NotFound(identifier.to_string()))
     }
 }
 
@@ -166,7 +1224,7 @@ impl Rustlink {
 pub struct RustlinkJS {
     rustlink: Rustlink,
     callback: Function,
-    receiver: Receiver<Round>,
+    receiver: Receiver<Event>,
 }
 
 cfg_if! {
@@ -203,7 +1261,13 @@ impl RustlinkJS {
     /// - `fetch_interval_seconds`: How often to update data points (to prevent RPC rate limitation)
     /// - `contracts`: A list of tuples containing a ticker name and its corresponding contract address on the EVM chain
     /// - `callback`: A JavaScript function (async or sync) that will be called every time a new data point is fetched
+    ///   or a fetch fails. Its `type` is `Round`, `Failed` with the `identifier` and `error` of the failure, or
+    ///   `Disagreement` if the providers of a quorum did not agree.
+    ///   The signed `answer` of the round is passed as a decimal string to preserve its precision.
     /// - `call_timeout_seconds`: The timeout for each contract call in seconds
+    ///
+    /// Throws a descriptive error if the RPC url or a contract address is invalid, an identifier is
+    /// used twice or the fetch interval is `0`.
     /// ```javascript
     /// import init, { RustlinkJS } from '../web/rustlink.js';
     ///
@@ -240,10 +1304,9 @@ impl RustlinkJS {
         contracts: Contracts,
         callback: Function,
         call_timeout_seconds: u64,
-    ) -> Self {
-        // Cast `JsValue` to `Function`
-
-        let contracts: Vec<(String, String)> = from_value(contracts.into()).unwrap();
+    ) -> Result<RustlinkJS, JsValue> {
+        let contracts: Vec<(String, String)> = from_value(contracts.into())
+            .map_err(|e| JsValue::from_str(&format!("Invalid contracts: {}", e)))?;
 
         let (sender, receiver) = async_std::channel::unbounded();
         let reflector = Reflector::Sender(sender);
@@ -254,14 +1317,13 @@ impl RustlinkJS {
             contracts,
             std::time::Duration::from_secs(call_timeout_seconds),
         )
-        .map_err(|e| JsValue::from_str(&format!("{}", e)))
-        .unwrap();
+        .map_err(|e| JsValue::from_str(&format!("{}", e)))?;
 
-        RustlinkJS {
+        Ok(RustlinkJS {
             rustlink,
             callback,
             receiver,
-        }
+        })
     }
 
     /// Starts the RustlinkJS instance.
@@ -272,10 +1334,16 @@ impl RustlinkJS {
         let receiver = self.receiver.clone();
         let callback = self.callback.clone();
         spawn_local(async move {
-            while let Ok(round) = receiver.recv().await {
+            while let Ok(event) = receiver.recv().await {
                 // Prepare arguments to pass to JS function
                 let this = JsValue::NULL; // 'this' context for function, null in this case
-                let arg_js = to_value(&round).unwrap();
+                let arg_js = match to_value(&event) {
+                    Ok(arg_js) => arg_js,
+                    Err(error) => {
+                        log::error!("Failed converting event: {}", error);
+                        continue;
+                    }
+                };
 
                 // Call the function
                 let _ = callback.call1(&this, &arg_js);
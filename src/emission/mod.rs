@@ -0,0 +1,180 @@
+use async_std::sync::RwLock;
+use ethers::types::I256;
+use std::{collections::HashMap, sync::Arc};
+use workflow_rs::core::time::unixtime_as_millis_u64;
+
+use crate::interface::Round;
+
+/// Decides which fetched rounds of a feed are passed on to the reflector.
+///
+/// By default every fetched round is emitted. Each enabled condition further restricts
+/// the emitted rounds, unless `force_after_seconds` have passed since the last emission.
+/// The first round of a feed is always emitted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EmissionPolicy {
+    /// Only emit rounds with a different round id than the last emitted one
+    pub new_round_only: bool,
+    /// Only emit rounds whose answer deviates more than this many basis points
+    /// from the last emitted answer
+    pub deviation_basis_points: Option<u32>,
+    /// Always emit a round once this many seconds have passed since the last emission
+    pub force_after_seconds: Option<u64>,
+}
+
+/// The last round that was emitted for a feed
+#[derive(Clone, Debug)]
+pub(crate) struct Emitted {
+    round: Round,
+    /// Unix timestamp in milliseconds of when the round was emitted
+    emitted_at: u64,
+}
+
+impl EmissionPolicy {
+    /// Only emit rounds with a new round id
+    pub fn on_new_round() -> Self {
+        EmissionPolicy {
+            new_round_only: true,
+            ..Default::default()
+        }
+    }
+
+    /// Only emit rounds whose answer deviates more than `basis_points` from the last emitted answer
+    pub fn on_deviation_basis_points(basis_points: u32) -> Self {
+        EmissionPolicy {
+            deviation_basis_points: Some(basis_points),
+            ..Default::default()
+        }
+    }
+
+    /// Only emit rounds whose answer deviates more than `percent` from the last emitted answer
+    pub fn on_deviation_percent(percent: u32) -> Self {
+        EmissionPolicy::on_deviation_basis_points(percent.saturating_mul(100))
+    }
+
+    /// Additionally emit a round whenever `seconds` have passed since the last emission
+    pub fn force_after_seconds(mut self, seconds: u64) -> Self {
+        self.force_after_seconds = Some(seconds);
+        self
+    }
+
+    /// Whether `round` should be emitted at `now` (unix timestamp in milliseconds)
+    /// given the last emitted round of its feed
+    pub(crate) fn should_emit(&self, last: Option<&Emitted>, round: &Round, now: u64) -> bool {
+        let Some(last) = last else {
+            return true;
+        };
+
+        if let Some(seconds) = self.force_after_seconds {
+            if now.saturating_sub(last.emitted_at) >= seconds.saturating_mul(1000) {
+                return true;
+            }
+        }
+
+        if self.new_round_only && round.round_id == last.round.round_id {
+            return false;
+        }
+
+        match self.deviation_basis_points {
+            Some(basis_points) => deviates(&last.round, round, basis_points),
+            None => true,
+        }
+    }
+}
+
+/// Whether the answer of `round` deviates more than `basis_points` from the answer of `last`
+fn deviates(last: &Round, round: &Round, basis_points: u32) -> bool {
+    let decimals = last.decimals.max(round.decimals);
+    let (Some(last_answer), Some(answer)) = (
+        last.price().to_scaled(decimals),
+        round.price().to_scaled(decimals),
+    ) else {
+        return true;
+    };
+
+    // |answer - last| / |last| > basis_points / 10000, without dividing
+    let difference = answer
+        .checked_sub(last_answer)
+        .and_then(|difference| difference.checked_mul(I256::from(10000)))
+        .map(I256::unsigned_abs);
+    let threshold = last_answer
+        .checked_mul(I256::from(basis_points))
+        .map(I256::unsigned_abs);
+    match (difference, threshold) {
+        (Some(difference), Some(threshold)) => difference > threshold,
+        // Overflows only happen for huge differences
+        _ => true,
+    }
+}
+
+/// Keeps track of the last emitted round of every feed, by identifier.
+/// Clones share the same underlying state.
+#[derive(Clone, Default)]
+pub struct Emissions {
+    last: Arc<RwLock<HashMap<String, Emitted>>>,
+}
+
+impl Emissions {
+    /// Checks whether `round` should be emitted according to `policy`,
+    /// and records it as the last emitted round of its feed if so
+    pub async fn emit(&self, policy: &EmissionPolicy, round: &Round) -> bool {
+        let now = unixtime_as_millis_u64();
+        let mut last = self.last.write().await;
+        if !policy.should_emit(last.get(&round.identifier), round, now) {
+            return false;
+        }
+        last.insert(
+            round.identifier.clone(),
+            Emitted {
+                round: round.clone(),
+                emitted_at: now,
+            },
+        );
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::emission::{EmissionPolicy, Emitted};
+    use crate::interface::Round;
+    use crate::test_utils::round_answered;
+
+    fn emitted(round: Round, emitted_at: u64) -> Emitted {
+        Emitted { round, emitted_at }
+    }
+
+    #[test]
+    fn emit_on_new_round() {
+        let policy = EmissionPolicy::on_new_round();
+        let last = emitted(round_answered("ETH", 1, 300000000000), 0);
+
+        assert!(policy.should_emit(None, &round_answered("ETH", 1, 300000000000), 0));
+        assert!(!policy.should_emit(Some(&last), &round_answered("ETH", 1, 300000000000), 1000));
+        assert!(policy.should_emit(Some(&last), &round_answered("ETH", 2, 300000000000), 1000));
+    }
+
+    #[test]
+    fn emit_on_deviation() {
+        let policy = EmissionPolicy::on_deviation_percent(1);
+        let last = emitted(round_answered("ETH", 1, 300000000000), 0);
+
+        // Exactly 1% is not more than the threshold
+        assert!(!policy.should_emit(Some(&last), &round_answered("ETH", 2, 303000000000), 1000));
+        assert!(policy.should_emit(Some(&last), &round_answered("ETH", 2, 303000000001), 1000));
+        assert!(policy.should_emit(Some(&last), &round_answered("ETH", 2, 296999999999), 1000));
+
+        let last = emitted(round_answered("ETH", 1, 0), 0);
+        assert!(policy.should_emit(Some(&last), &round_answered("ETH", 2, 1), 1000));
+        assert!(!policy.should_emit(Some(&last), &round_answered("ETH", 2, 0), 1000));
+    }
+
+    #[test]
+    fn emit_when_forced() {
+        let policy = EmissionPolicy::on_deviation_basis_points(50).force_after_seconds(60);
+        let last = emitted(round_answered("ETH", 1, 300000000000), 0);
+
+        assert!(!policy.should_emit(Some(&last), &round_answered("ETH", 1, 300000000000), 59999));
+        assert!(policy.should_emit(Some(&last), &round_answered("ETH", 1, 300000000000), 60000));
+    }
+}
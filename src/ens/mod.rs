@@ -0,0 +1,145 @@
+use async_std::future::timeout;
+use ethers::{
+    contract::ContractError,
+    providers::{Middleware, Provider},
+    types::Address,
+};
+use futures::StreamExt;
+use std::time::Duration;
+
+use crate::core::{Feed, Rustlink};
+use crate::failover::Failover;
+use crate::fetcher::refresh_feed_metadata;
+use crate::interface::ContractCallError;
+
+/// How often the ENS names of feeds are resolved again
+pub const ENS_REFRESH_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Whether a contract entry is an ENS name such as `eth-usd.data.eth` rather than an address
+pub fn is_ens_name(entry: &str) -> bool {
+    !entry.starts_with("0x")
+        && entry.contains('.')
+        && entry
+            .split('.')
+            .all(|label| !label.is_empty() && !label.contains(char::is_whitespace))
+}
+
+/// Resolves the ENS name of a feed through the provider of the instance
+/// and stores the address it resolved to
+pub async fn resolve_feed_address<'a>(
+    rustlink: &'a Rustlink,
+    identifier: &str,
+    ens_name: &str,
+) -> Result<Address, ContractCallError<&'a Provider<Failover>>> {
+    let provider = &rustlink.configuration.provider;
+    let address = timeout(
+        rustlink.configuration.call_timeout,
+        provider.resolve_name(ens_name),
+    )
+    .await?
+    .map_err(ContractError::from_middleware_error)?;
+
+    rustlink
+        .ens_addresses
+        .write()
+        .await
+        .insert(identifier.to_string(), address);
+    Ok(address)
+}
+
+/// The address of a feed. Feeds given by an ENS name are resolved on first use,
+/// and use the address they last resolved to from then on.
+pub async fn feed_address<'a>(
+    rustlink: &'a Rustlink,
+    feed: &Feed,
+) -> Result<Address, ContractCallError<&'a Provider<Failover>>> {
+    let Some(ref ens_name) = feed.ens_name else {
+        return Ok(feed.address);
+    };
+    let cached = rustlink
+        .ens_addresses
+        .read()
+        .await
+        .get(&feed.identifier)
+        .copied();
+    match cached {
+        Some(address) => Ok(address),
+        None => resolve_feed_address(rustlink, &feed.identifier, ens_name).await,
+    }
+}
+
+/// Resolves the ENS names of the feeds again every `ENS_REFRESH_INTERVAL`, so feeds follow
+/// their name when it is pointed to a new contract. Runs indefinitely.
+pub async fn refresh_ens_names(rustlink: &Rustlink) {
+    let mut ticks = workflow_rs::core::task::interval(ENS_REFRESH_INTERVAL);
+    while ticks.next().await.is_some() {
+        for feed in &rustlink.configuration.contracts {
+            let Some(ref ens_name) = feed.ens_name else {
+                continue;
+            };
+            let previous = rustlink
+                .ens_addresses
+                .read()
+                .await
+                .get(&feed.identifier)
+                .copied();
+            let address = match resolve_feed_address(rustlink, &feed.identifier, ens_name).await {
+                Ok(address) => address,
+                Err(error) => {
+                    log::error!("Failed resolving {}: {}", ens_name, error);
+                    continue;
+                }
+            };
+            if previous.is_some_and(|previous| previous != address) {
+                log::info!("{} now resolves to {:?}", ens_name, address);
+                // The new contract may report in different decimals.
+                if let Err(error) = refresh_feed_metadata(rustlink, &feed.identifier, address).await
+                {
+                    log::error!("Failed loading metadata of {}: {}", feed.identifier, error);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use ethers::types::Address;
+
+    use crate::ens::{feed_address, is_ens_name};
+    use crate::test_utils::rustlink_with;
+
+    #[tokio::test]
+    async fn ens_names_told_apart_from_addresses() {
+        assert!(is_ens_name("eth-usd.data.eth"));
+        assert!(!is_ens_name("0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e"));
+        assert!(!is_ens_name("0x9ef1"));
+        assert!(!is_ens_name("eth-usd..eth"));
+        assert!(!is_ens_name("eth usd.eth"));
+
+        let contracts = vec![
+            (
+                "ETH".to_string(),
+                "0x5f4eC3Df9cbd43714FE2740f5E3616155c5b8419".to_string(),
+            ),
+            ("BTC".to_string(), "btc-usd.data.eth".to_string()),
+        ];
+        let (rustlink, _receiver) = rustlink_with("https://eth.llamarpc.com", contracts);
+
+        let eth = &rustlink.configuration.contracts[0];
+        let btc = &rustlink.configuration.contracts[1];
+        assert!(eth.ens_name.is_none());
+        assert_eq!(btc.ens_name.as_deref(), Some("btc-usd.data.eth"));
+        assert_eq!(feed_address(&rustlink, eth).await.unwrap(), eth.address);
+
+        // Resolved names are not resolved again until they are refreshed.
+        let resolved = Address::repeat_byte(0x11);
+        rustlink
+            .ens_addresses
+            .write()
+            .await
+            .insert("BTC".to_string(), resolved);
+        assert_eq!(feed_address(&rustlink, btc).await.unwrap(), resolved);
+    }
+}
@@ -1,9 +1,39 @@
+use ethers::providers::Middleware;
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+use crate::event::FetchError;
+use crate::interface::ContractCallError;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum Error {
-    #[error("Could not find symbol")]
-    NotFound,
-    #[error("Could not deserialize binary data")]
-    Deserialize,
+    #[error("Invalid RPC url {url}: {reason}")]
+    InvalidRpcUrl { url: String, reason: String },
+    #[error("Invalid contract address {address} for {identifier}")]
+    InvalidAddress { identifier: String, address: String },
+    #[error("Identifier {0} is used by more than one contract")]
+    DuplicateIdentifier(String),
+    #[error("Fetch interval must be at least one second")]
+    ZeroInterval,
+    #[error(
+        "Quorum threshold {threshold} must be a majority of at least two providers, got {providers}"
+    )]
+    InvalidQuorum { threshold: usize, providers: usize },
+    #[error("No contract with identifier {0}")]
+    NotFound(String),
+    #[error("Contract {0} is read through a Feed Registry, which has no round history to walk")]
+    RegistryFeed(String),
+    #[error("Contract call failed: {0}")]
+    Fetch(#[from] FetchError),
+    #[error("No round found at or before timestamp {0}")]
+    RoundNotFound(u64),
+    #[error("Rustlink is not running")]
+    NotRunning,
+    #[error("No {pair} feed on {chain} in the catalog")]
+    NotInCatalog { chain: String, pair: String },
+}
+
+impl<M: Middleware> From<ContractCallError<M>> for Error {
+    fn from(error: ContractCallError<M>) -> Self {
+        Error::Fetch((&error).into())
+    }
 }
@@ -0,0 +1,162 @@
+use ethers::{
+    contract::{ContractError, MulticallError},
+    providers::Middleware,
+    types::Bytes,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::interface::{ContractCallError, Round};
+
+/// Why fetching the latest round of a feed failed
+#[derive(Error, Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", content = "message")]
+pub enum FetchError {
+    /// The call did not complete within the call timeout
+    #[error("Call timed out")]
+    Timeout,
+    /// The call could not be encoded, or its output could not be decoded
+    #[error("Abi error: {0}")]
+    Abi(String),
+    /// The contract reverted the call, with the revert data
+    #[error("Call reverted: {0}")]
+    Revert(Bytes),
+    /// The RPC could not be reached or returned an error
+    #[error("Transport error: {0}")]
+    Transport(String),
+}
+
+impl<M: Middleware> From<&ContractError<M>> for FetchError {
+    fn from(error: &ContractError<M>) -> Self {
+        match error {
+            ContractError::DecodingError(_)
+            | ContractError::AbiError(_)
+            | ContractError::DetokenizationError(_) => FetchError::Abi(error.to_string()),
+            ContractError::Revert(data) => FetchError::Revert(data.clone()),
+            ContractError::MiddlewareError { .. }
+            | ContractError::ProviderError { .. }
+            | ContractError::ConstructorError
+            | ContractError::ContractNotDeployed => FetchError::Transport(error.to_string()),
+        }
+    }
+}
+
+impl<M: Middleware> From<&ContractCallError<M>> for FetchError {
+    fn from(error: &ContractCallError<M>) -> Self {
+        match error {
+            ContractCallError::Timeout(_) => FetchError::Timeout,
+            ContractCallError::Abi(_) | ContractCallError::InvalidOutput(_) => {
+                FetchError::Abi(error.to_string())
+            }
+            ContractCallError::Revert(data) => FetchError::Revert(data.clone()),
+            ContractCallError::Contract(error) => error.into(),
+            ContractCallError::Multicall(MulticallError::ContractError(error)) => error.into(),
+            ContractCallError::Multicall(_) => FetchError::Transport(error.to_string()),
+            ContractCallError::Retried { last, .. } => last.as_ref().into(),
+        }
+    }
+}
+
+/// What the fetcher reports about a feed: a new round, a failed fetch or, when reading
+/// through a quorum of providers, a disagreement between them.
+///
+/// Failures are reported on every failed fetch, so consumers can tell a feed that
+/// did not move apart from one that could not be reached.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum Event {
+    /// A round was fetched
+    Round(Round),
+    /// Fetching the latest round of a feed failed
+    Failed {
+        /// Identifier of the feed
+        identifier: String,
+        /// Why the fetch failed
+        error: FetchError,
+        /// Number of attempts that were made before giving up
+        attempts: u32,
+    },
+    /// Not enough providers of the quorum reported the same round
+    Disagreement {
+        /// Identifier of the feed
+        identifier: String,
+        /// The rounds reported by the providers that responded
+        rounds: Vec<Round>,
+        /// Number of providers that failed to respond
+        failures: usize,
+    },
+}
+
+impl Event {
+    /// Identifier of the feed the event is about
+    pub fn identifier(&self) -> &str {
+        match self {
+            Event::Round(round) => &round.identifier,
+            Event::Failed { identifier, .. } | Event::Disagreement { identifier, .. } => identifier,
+        }
+    }
+
+    /// The fetched round, if the fetch succeeded
+    pub fn round(&self) -> Option<&Round> {
+        match self {
+            Event::Round(round) => Some(round),
+            Event::Failed { .. } | Event::Disagreement { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use async_std::future::timeout;
+    use ethers::{
+        abi::InvalidOutputType,
+        contract::ContractError,
+        providers::{Http, Provider, ProviderError},
+        types::Bytes,
+    };
+    use std::time::Duration;
+
+    use crate::event::FetchError;
+    use crate::interface::ContractCallError;
+
+    type CallError = ContractCallError<Provider<Http>>;
+
+    #[tokio::test]
+    async fn failures_classified() {
+        let elapsed = timeout(Duration::ZERO, futures::future::pending::<()>())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            FetchError::from(&CallError::Timeout(elapsed)),
+            FetchError::Timeout
+        );
+
+        let invalid = CallError::InvalidOutput(InvalidOutputType("Expected a tuple".into()));
+        assert!(matches!(FetchError::from(&invalid), FetchError::Abi(_)));
+
+        let data = Bytes::from(vec![0x08, 0xc3, 0x79, 0xa0]);
+        let revert = CallError::Contract(ContractError::Revert(data.clone()));
+        assert_eq!(FetchError::from(&revert), FetchError::Revert(data));
+
+        let transport = CallError::Contract(ContractError::ProviderError {
+            e: ProviderError::CustomError("Connection refused".to_string()),
+        });
+        assert!(matches!(
+            FetchError::from(&transport),
+            FetchError::Transport(_)
+        ));
+        assert!(transport.is_transient());
+        assert!(!revert.is_transient());
+
+        let retried = CallError::Retried {
+            attempts: 3,
+            last: Box::new(transport),
+        };
+        assert_eq!(retried.attempts(), 3);
+        assert!(matches!(
+            FetchError::from(&retried),
+            FetchError::Transport(_)
+        ));
+    }
+}
@@ -0,0 +1,349 @@
+use async_std::future::timeout;
+use async_trait::async_trait;
+use ethers::providers::{
+    Http, HttpClientError, JsonRpcClient, JsonRpcError, ProviderError, RpcError,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::Debug,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use thiserror::Error;
+use workflow_rs::core::time::unixtime_as_millis_u64;
+
+use crate::error::Error;
+
+/// How long an endpoint is skipped after its first failure, doubled for every further failure
+const BASE_COOLDOWN: Duration = Duration::from_secs(5);
+
+/// The longest an endpoint is skipped for
+const MAX_COOLDOWN: Duration = Duration::from_secs(300);
+
+#[derive(Error, Debug)]
+pub enum FailoverError {
+    #[error(transparent)]
+    Http(#[from] HttpClientError),
+    #[error("Request to {0} timed out")]
+    Timeout(String),
+    #[error("Could not serialize params: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("No RPC endpoints configured")]
+    NoEndpoints,
+}
+
+impl RpcError for FailoverError {
+    fn as_error_response(&self) -> Option<&JsonRpcError> {
+        match self {
+            FailoverError::Http(error) => error.as_error_response(),
+            _ => None,
+        }
+    }
+
+    fn as_serde_error(&self) -> Option<&serde_json::Error> {
+        match self {
+            FailoverError::Http(error) => error.as_serde_error(),
+            FailoverError::SerdeJson(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<FailoverError> for ProviderError {
+    fn from(error: FailoverError) -> Self {
+        match error {
+            FailoverError::Http(error) => error.into(),
+            error => ProviderError::JsonRpcClientError(Box::new(error)),
+        }
+    }
+}
+
+/// The health of a single RPC endpoint as seen by the requests sent to it
+#[derive(Debug, Default)]
+struct Health {
+    /// Number of requests that failed in a row
+    failures: AtomicU32,
+    /// Unix timestamp in milliseconds until which the endpoint is skipped
+    skipped_until: AtomicU64,
+}
+
+impl Health {
+    fn is_healthy(&self, now: u64) -> bool {
+        self.skipped_until.load(Ordering::Relaxed) <= now
+    }
+
+    fn succeeded(&self) {
+        self.failures.store(0, Ordering::Relaxed);
+        self.skipped_until.store(0, Ordering::Relaxed);
+    }
+
+    /// Records a failed request. Requests that were sent before the endpoint started cooling
+    /// down fail together, so only the first failure of every cooldown is counted.
+    fn failed(&self, now: u64) {
+        let skipped_until = self.skipped_until.load(Ordering::Relaxed);
+        if skipped_until > now {
+            return;
+        }
+        let failures = self.failures.load(Ordering::Relaxed).saturating_add(1);
+        let cooldown = BASE_COOLDOWN
+            .saturating_mul(2u32.saturating_pow(failures - 1))
+            .min(MAX_COOLDOWN);
+        let cooling_down = self.skipped_until.compare_exchange(
+            skipped_until,
+            now.saturating_add(cooldown.as_millis() as u64),
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        );
+        if cooling_down.is_ok() {
+            self.failures.store(failures, Ordering::Relaxed);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    url: String,
+    client: Http,
+    health: Health,
+}
+
+/// The health of an RPC endpoint
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EndpointHealth {
+    /// The RPC url of the endpoint
+    pub url: String,
+    /// Whether requests are currently sent to the endpoint. Unhealthy endpoints are
+    /// only used when every endpoint is unhealthy.
+    pub healthy: bool,
+    /// Number of requests that failed in a row
+    pub consecutive_failures: u32,
+}
+
+/// A JSON-RPC client that sends every request to the first healthy endpoint of an ordered
+/// list of HTTP endpoints, failing over to the next one on timeouts and errors.
+///
+/// A failing endpoint is skipped for a cooldown that grows with every failure in a row.
+/// Once the cooldown has passed the endpoint is tried again before the ones after it, so
+/// requests move back to the preferred endpoint as soon as it recovers. Reverted calls are
+/// answers of the contract rather than failures of the endpoint, so they are not failed over.
+///
+/// Clones share the same endpoints and health.
+#[derive(Clone, Debug)]
+pub struct Failover {
+    endpoints: Arc<Vec<Endpoint>>,
+    /// How long to wait for a single endpoint before failing over
+    request_timeout: Duration,
+}
+
+impl Failover {
+    /// Creates a client for the given RPC urls, in order of preference.
+    ///
+    /// Returns `Error::InvalidRpcUrl` if an url cannot be parsed.
+    pub fn new<I, S>(rpc_urls: I, request_timeout: Duration) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let endpoints = rpc_urls
+            .into_iter()
+            .map(|url| {
+                let url = url.as_ref();
+                let client = Http::from_str(url).map_err(|error| Error::InvalidRpcUrl {
+                    url: url.to_string(),
+                    reason: error.to_string(),
+                })?;
+                Ok(Endpoint {
+                    url: url.to_string(),
+                    client,
+                    health: Health::default(),
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Failover {
+            endpoints: Arc::new(endpoints),
+            request_timeout,
+        })
+    }
+
+    /// How long to wait for a single endpoint before failing over
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout
+    }
+
+    /// The same endpoints, sharing their health, with another timeout for a single endpoint
+    pub fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
+    /// The RPC urls of the endpoints, in order of preference
+    pub fn urls(&self) -> Vec<String> {
+        self.endpoints
+            .iter()
+            .map(|endpoint| endpoint.url.clone())
+            .collect()
+    }
+
+    /// The health of every endpoint, in order of preference
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        let now = unixtime_as_millis_u64();
+        self.endpoints
+            .iter()
+            .map(|endpoint| EndpointHealth {
+                url: endpoint.url.clone(),
+                healthy: endpoint.health.is_healthy(now),
+                consecutive_failures: endpoint.health.failures.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    /// The endpoints in the order they should be tried: the healthy ones in order of
+    /// preference, then the unhealthy ones in the order they become healthy again
+    fn by_preference(&self, now: u64) -> Vec<&Endpoint> {
+        let (mut endpoints, mut unhealthy): (Vec<_>, Vec<_>) = self
+            .endpoints
+            .iter()
+            .partition(|endpoint| endpoint.health.is_healthy(now));
+        unhealthy.sort_by_key(|endpoint| endpoint.health.skipped_until.load(Ordering::Relaxed));
+        endpoints.append(&mut unhealthy);
+        endpoints
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl JsonRpcClient for Failover {
+    type Error = FailoverError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, FailoverError>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        // Every endpoint is sent the same params, so they are only serialized once.
+        let params = serde_json::to_value(params)?;
+        let now = unixtime_as_millis_u64();
+        let mut last_error = FailoverError::NoEndpoints;
+
+        for endpoint in self.by_preference(now) {
+            let request = endpoint.client.request(method, params.clone());
+            let error = match timeout(self.request_timeout, request).await {
+                Ok(Ok(response)) => {
+                    endpoint.health.succeeded();
+                    return Ok(response);
+                }
+                Ok(Err(error))
+                    if error
+                        .as_error_response()
+                        .is_some_and(JsonRpcError::is_revert) =>
+                {
+                    endpoint.health.succeeded();
+                    return Err(error.into());
+                }
+                Ok(Err(error)) => FailoverError::Http(error),
+                Err(_) => FailoverError::Timeout(endpoint.url.clone()),
+            };
+            log::warn!("RPC endpoint {} failed: {}", endpoint.url, error);
+            endpoint.health.failed(unixtime_as_millis_u64());
+            last_error = error;
+        }
+        Err(last_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use async_std::future::timeout;
+    use ethers::providers::{Middleware, Provider};
+    use futures::future::join_all;
+    use std::time::Duration;
+
+    use crate::failover::{Failover, Health};
+    use crate::test_utils::{hang, serve};
+
+    /// Answers the chain id of BSC to every request, returns its url
+    async fn serve_chain_id() -> String {
+        serve(|_, _| Some("0x38".to_string())).await
+    }
+
+    #[test]
+    fn cooldown_grows_with_failures() {
+        let health = Health::default();
+        health.failed(0);
+        assert!(!health.is_healthy(4999));
+        assert!(health.is_healthy(5000));
+
+        // Failures during the cooldown are not counted
+        health.failed(1000);
+        health.failed(5000);
+        assert!(!health.is_healthy(14999));
+        assert!(health.is_healthy(15000));
+
+        let mut now = 15000;
+        for _ in 0..20 {
+            health.failed(now);
+            now += 300000;
+        }
+        assert!(health.is_healthy(now));
+        assert!(!health.is_healthy(now - 1));
+
+        health.succeeded();
+        assert!(health.is_healthy(0));
+    }
+
+    #[tokio::test]
+    async fn failover_to_next_endpoint() {
+        let backup = serve_chain_id().await;
+        // Nothing listens on the discard port, so the preferred endpoint refuses connections.
+        let failover =
+            Failover::new(["http://127.0.0.1:9", &backup], Duration::from_secs(5)).unwrap();
+        let provider = Provider::new(failover.clone());
+
+        assert_eq!(provider.get_chainid().await.unwrap().as_u64(), 56);
+        let health = failover.health();
+        assert!(!health[0].healthy);
+        assert_eq!(health[0].consecutive_failures, 1);
+        assert!(health[1].healthy);
+
+        // The preferred endpoint is skipped while it cools down.
+        assert_eq!(provider.get_chainid().await.unwrap().as_u64(), 56);
+        assert_eq!(failover.health()[0].consecutive_failures, 1);
+    }
+
+    #[tokio::test]
+    async fn concurrent_failures_counted_once() {
+        let backup = serve_chain_id().await;
+        let failover =
+            Failover::new(["http://127.0.0.1:9", &backup], Duration::from_secs(5)).unwrap();
+        let provider = Provider::new(failover.clone());
+
+        let requests = (0..10).map(|_| provider.get_chainid());
+        for chain_id in join_all(requests).await {
+            assert_eq!(chain_id.unwrap().as_u64(), 56);
+        }
+        assert_eq!(failover.health()[0].consecutive_failures, 1);
+    }
+
+    #[tokio::test]
+    async fn failover_within_call_timeout() {
+        let backup = serve_chain_id().await;
+        let failover = Failover::new([hang().await, backup], Duration::from_millis(200)).unwrap();
+        let provider = Provider::new(failover.clone());
+
+        // A call bounded by a longer timeout outlasts the hanging endpoint and reaches the next one.
+        let chain_id = timeout(Duration::from_secs(1), provider.get_chainid()).await;
+        assert_eq!(chain_id.unwrap().unwrap().as_u64(), 56);
+        assert!(!failover.health()[0].healthy);
+
+        // Each endpoint is waited for on its own, regardless of how many endpoints there are.
+        let failover = failover.with_request_timeout(Duration::from_secs(2));
+        assert_eq!(failover.request_timeout(), Duration::from_secs(2));
+        assert_eq!(failover.health()[0].consecutive_failures, 1);
+    }
+}
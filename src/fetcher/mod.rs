@@ -1,72 +1,1033 @@
+use std::collections::HashSet;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
 use std::time::Duration;
 
-use async_std::stream::StreamExt;
-use ethers::providers::{Http, Provider};
+use ethers::providers::Provider;
 use ethers::types::Address;
-use futures::{select, FutureExt};
+use futures::{
+    future::{self, join_all},
+    select, stream, FutureExt, Stream, StreamExt,
+};
+use workflow_rs::core::time::unixtime_as_millis_u64;
 
-use super::interface::{ChainlinkContract, Round};
-use crate::core::Reflector::Sender;
-use crate::core::{Configuration, Rustlink};
+use super::interface::{ChainlinkContract, FeedMetadata, Round};
+use crate::core::Reflector::{Broadcast, Callback, Sender, Watch};
+use crate::core::{Configuration, Feed, Rustlink, StaleFeed};
+use crate::ens::{feed_address, refresh_ens_names};
+use crate::event::{Event, FetchError};
+use crate::failover::Failover;
 use crate::interface::ContractCallError;
+use crate::multicall::fetch_latest_rounds;
+use crate::quorum::Quorum;
+use crate::registry::FeedRegistry;
+use crate::websocket::follow_logs;
 
-/// Retrieves the price of an underlying asset from a particular contract
-async fn fetch_round_data_for_contract<'a>(
-    rustlink_configuration: &'a Configuration,
+/// The most blocks whose logs are requested at once, as RPCs limit the range of `eth_getLogs`
+const MAX_LOG_BLOCK_RANGE: u64 = 1000;
+
+/// The identifiers of the feeds whose fetch is still running
+#[derive(Default)]
+struct InFlight(Mutex<HashSet<String>>);
+
+impl InFlight {
+    /// Marks a feed as being fetched, returns `false` if it already is
+    fn start(&self, feed: &Feed) -> bool {
+        self.0.lock().unwrap().insert(feed.identifier.clone())
+    }
+
+    /// Marks a feed as no longer being fetched
+    fn finish(&self, feed: &Feed) {
+        self.0.lock().unwrap().remove(&feed.identifier);
+    }
+}
+
+/// How far the logs of a feed have been read
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LogCursor {
+    /// The last block whose logs have been read
+    pub block: u64,
+    /// The last block at which the proxy was seen pointing to the aggregator being read
+    pub verified_block: u64,
+}
+
+/// Loads the metadata of a feed from its contract, or from the registry it is read
+/// through, and stores it in the cache
+pub async fn refresh_feed_metadata<'a>(
+    rustlink: &'a Rustlink,
+    identifier: &'a str,
+    address: Address,
+) -> Result<FeedMetadata, ContractCallError<&'a Provider<Failover>>> {
+    let configuration = &rustlink.configuration;
+    let registry = configuration
+        .contracts
+        .iter()
+        .find(|feed| feed.identifier == identifier)
+        .and_then(|feed| feed.registry);
+    let metadata = match registry {
+        Some(pair) => {
+            FeedRegistry::new(
+                &configuration.provider,
+                pair.registry,
+                configuration.call_timeout,
+            )
+            .metadata(pair.base, pair.quote)
+            .await?
+        }
+        None => {
+            FeedMetadata::load(
+                &configuration.provider,
+                address,
+                configuration.call_timeout,
+                configuration.retry,
+            )
+            .await?
+        }
+    };
+
+    rustlink
+        .feed_metadata
+        .write()
+        .await
+        .insert(identifier.to_string(), metadata.clone());
+    Ok(metadata)
+}
+
+/// Retrieves the cached metadata of a feed, loading it if it is not cached yet
+pub async fn feed_metadata<'a>(
+    rustlink: &'a Rustlink,
     identifier: &'a str,
     address: Address,
-) -> Result<Round, ContractCallError<&'a Provider<Http>>> {
-    let contract = ChainlinkContract::new(
-        &rustlink_configuration.provider,
+) -> Result<FeedMetadata, ContractCallError<&'a Provider<Failover>>> {
+    let cached = rustlink.feed_metadata.read().await.get(identifier).cloned();
+    match cached {
+        Some(metadata) => Ok(metadata),
+        None => refresh_feed_metadata(rustlink, identifier, address).await,
+    }
+}
+
+/// Creates a contract instance for a feed using its cached decimals
+pub async fn cached_contract<'a>(
+    rustlink: &'a Rustlink,
+    identifier: &'a str,
+    address: Address,
+) -> Result<ChainlinkContract<'a>, ContractCallError<&'a Provider<Failover>>> {
+    let metadata = feed_metadata(rustlink, identifier, address).await?;
+    Ok(ChainlinkContract::with_decimals(
+        &rustlink.configuration.provider,
         identifier,
         address,
-        rustlink_configuration.call_timeout,
+        metadata.decimals,
+        rustlink.configuration.call_timeout,
     )
-    .await?;
-    contract.latest_round_data().await
+    .with_retry(rustlink.configuration.retry))
+}
+
+/// Retrieves the price of an underlying asset from a particular contract
+async fn fetch_round_data_for_contract<'a>(
+    rustlink: &'a Rustlink,
+    feed: &'a Feed,
+    address: Address,
+) -> Result<Round, ContractCallError<&'a Provider<Failover>>> {
+    let identifier = &feed.identifier;
+    let metadata = feed_metadata(rustlink, identifier, address).await?;
+    let provider = &rustlink.configuration.provider;
+    let round = latest_round(rustlink, provider, feed, address, metadata.decimals).await?;
+    refresh_on_new_phase(rustlink, identifier, address, &metadata, round).await
+}
+
+/// Retrieves the latest round of a feed through the given provider, either from the contract
+/// of the feed or from the registry it is read through
+async fn latest_round<'a>(
+    rustlink: &'a Rustlink,
+    provider: &'a Provider<Failover>,
+    feed: &'a Feed,
+    address: Address,
+    decimals: u8,
+) -> Result<Round, ContractCallError<&'a Provider<Failover>>> {
+    let configuration = &rustlink.configuration;
+    match feed.registry {
+        Some(pair) => {
+            FeedRegistry::new(provider, pair.registry, configuration.call_timeout)
+                .with_retry(configuration.retry)
+                .latest_round(&feed.identifier, pair.base, pair.quote, decimals)
+                .await
+        }
+        None => {
+            ChainlinkContract::with_decimals(
+                provider,
+                &feed.identifier,
+                address,
+                decimals,
+                configuration.call_timeout,
+            )
+            .with_retry(configuration.retry)
+            .latest_round_data()
+            .await
+        }
+    }
+}
+
+/// Refreshes the cached metadata of a feed if the round was reported in a new phase.
+/// A new phase means the proxy points to a new aggregator, whose metadata may differ.
+/// Feeds that are not behind a proxy have no phases.
+pub async fn refresh_on_new_phase<'a>(
+    rustlink: &'a Rustlink,
+    identifier: &'a str,
+    address: Address,
+    metadata: &FeedMetadata,
+    mut round: Round,
+) -> Result<Round, ContractCallError<&'a Provider<Failover>>> {
+    if metadata
+        .phase_id
+        .is_some_and(|phase_id| phase_id != round.phase_id())
+    {
+        round.decimals = refresh_feed_metadata(rustlink, identifier, address)
+            .await?
+            .decimals;
+    }
+    Ok(round)
+}
+
+/// Marks the round as stale if it is older than the heartbeat of its feed,
+/// and emits a stale feed event for it
+async fn check_staleness(rustlink: &Rustlink, feed: &Feed, round: &mut Round) {
+    let Some(heartbeat_seconds) = feed.heartbeat_seconds else {
+        return;
+    };
+
+    let now = unixtime_as_millis_u64() / 1000;
+    let age_seconds = now.saturating_sub(round.updated_at.low_u64());
+    if age_seconds <= heartbeat_seconds {
+        return;
+    }
+
+    round.stale = true;
+    log::warn!(
+        "Feed {} is stale, last updated {} seconds ago",
+        round.identifier,
+        age_seconds
+    );
+    if let Some(ref sender) = rustlink.stale_sender {
+        let stale_feed = StaleFeed {
+            identifier: round.identifier.clone(),
+            round: round.clone(),
+            heartbeat_seconds,
+            age_seconds,
+        };
+        if let Err(error) = sender.send(stale_feed).await {
+            log::error!("Failed sending stale feed: {}", error);
+        }
+    }
+}
+
+/// Stores a round as the latest one of its feed and reflects it,
+/// if the emission policy of its feed allows it
+async fn reflect(rustlink: &Rustlink, round: Round) {
+    reflect_round(rustlink, round, true).await
+}
+
+/// Checks whether the stored latest round of a feed went stale while no newer round arrived,
+/// and marks it stale in the store if so
+async fn check_stored_staleness(rustlink: &Rustlink, feed: &Feed) {
+    let Some(mut round) = rustlink.store.latest(&feed.identifier).await else {
+        return;
+    };
+    check_staleness(rustlink, feed, &mut round).await;
+    if round.stale {
+        rustlink.store.mark_stale(&round).await;
+    }
+}
+
+/// Reflects rounds of a feed that were caught up on at once, oldest first.
+/// Only the newest of them tells whether the feed is stale by now, without any of them
+/// the stored round of the feed is checked instead.
+async fn reflect_caught_up(rustlink: &Rustlink, feed: &Feed, rounds: Vec<Round>) {
+    if rounds.is_empty() {
+        check_stored_staleness(rustlink, feed).await;
+        return;
+    }
+    let newest = rounds.len().saturating_sub(1);
+    for (index, round) in rounds.into_iter().enumerate() {
+        reflect_round(rustlink, round, index == newest).await;
+    }
+}
+
+async fn reflect_round(rustlink: &Rustlink, mut round: Round, check_stale: bool) {
+    // A fetch that finished after a later one reports a round that has been replaced already.
+    if rustlink.store.is_outdated(&round).await {
+        log::debug!(
+            "Ignoring outdated round {} of {}",
+            round.round_id,
+            round.identifier
+        );
+        return;
+    }
+    let feed = rustlink
+        .configuration
+        .contracts
+        .iter()
+        .find(|feed| feed.identifier == round.identifier);
+    if let Some(feed) = feed.filter(|_| check_stale) {
+        check_staleness(rustlink, feed, &mut round).await;
+    }
+    if !rustlink.store.update(round.clone()).await {
+        return;
+    }
+
+    if let Some(feed) = feed {
+        if !rustlink.emissions.emit(&feed.emission_policy, &round).await {
+            return;
+        }
+    }
+    dispatch(rustlink, Event::Round(round)).await;
+}
+
+/// Logs a failed fetch of a feed and reflects it
+async fn reflect_failure(rustlink: &Rustlink, identifier: &str, error: FetchError, attempts: u32) {
+    log::error!(
+        "Failed updating price of {} after {} attempts: {}",
+        identifier,
+        attempts,
+        error
+    );
+    let event = Event::Failed {
+        identifier: identifier.to_string(),
+        error,
+        attempts,
+    };
+    dispatch(rustlink, event).await;
+}
+
+/// Passes an event to the subscriptions and the configured reflector
+async fn dispatch(rustlink: &Rustlink, event: Event) {
+    rustlink.subscriptions.send(event.clone());
+    match rustlink.reflector {
+        Sender(ref sender) => {
+            // Attempt to send the event through the channel.
+            if let Err(error) = sender.send(event).await {
+                log::error!("Failed sending data: {}", error);
+            }
+        }
+        Broadcast(ref broadcast) => broadcast.send(event),
+        Watch(ref watch) => {
+            if let Event::Round(round) = event {
+                watch.send(round);
+            }
+        }
+        Callback(ref callback) => {
+            // Keep panics inside the callback from taking down the fetch loop.
+            let identifier = event.identifier().to_string();
+            let result = match panic::catch_unwind(AssertUnwindSafe(|| callback(event))) {
+                Ok(future) => AssertUnwindSafe(future).catch_unwind().await,
+                Err(panic) => Err(panic),
+            };
+            if result.is_err() {
+                log::error!("Callback panicked while handling {}", identifier);
+            }
+        }
+    }
+}
+
+/// Retrieves the round with the given id from a particular contract
+pub async fn fetch_historical_round_data_for_contract<'a>(
+    rustlink: &'a Rustlink,
+    identifier: &'a str,
+    address: Address,
+    round_id: u128,
+) -> Result<Round, ContractCallError<&'a Provider<Failover>>> {
+    let contract = cached_contract(rustlink, identifier, address).await?;
+    contract.round_data_at(round_id).await
+}
+
+/// Groups the configured feeds by their fetch interval in seconds
+fn schedules(configuration: &Configuration) -> Vec<(u64, Vec<&Feed>)> {
+    schedules_by(configuration, |feed| {
+        feed.fetch_interval_seconds(configuration)
+    })
+}
+
+/// Groups the configured feeds by their heartbeat in seconds, or by their fetch interval
+/// for feeds without a heartbeat
+fn staleness_schedules(configuration: &Configuration) -> Vec<(u64, Vec<&Feed>)> {
+    schedules_by(configuration, |feed| match feed.heartbeat_seconds {
+        // A timer needs an interval of at least one second.
+        Some(heartbeat_seconds) => heartbeat_seconds.max(1),
+        None => feed.fetch_interval_seconds(configuration),
+    })
+}
+
+/// Groups the configured feeds by the interval in seconds `interval` returns for them
+fn schedules_by(
+    configuration: &Configuration,
+    interval: impl Fn(&Feed) -> u64,
+) -> Vec<(u64, Vec<&Feed>)> {
+    let mut schedules: Vec<(u64, Vec<&Feed>)> = Vec::new();
+    for feed in &configuration.contracts {
+        let interval = interval(feed);
+        match schedules
+            .iter_mut()
+            .find(|(seconds, _)| *seconds == interval)
+        {
+            Some((_, feeds)) => feeds.push(feed),
+            None => schedules.push((interval, vec![feed])),
+        }
+    }
+    schedules
+}
+
+/// A stream that yields every group of feeds at its interval in seconds
+fn timers<'a>(schedules: Vec<(u64, Vec<&'a Feed>)>) -> impl Stream<Item = Vec<&'a Feed>> + 'a {
+    stream::select_all(schedules.into_iter().map(|(seconds, feeds)| {
+        workflow_rs::core::task::interval(Duration::from_secs(seconds)).map(move |_| feeds.clone())
+    }))
 }
 
 // The function signature looks good, but ensure all types (Rustlink, Round, etc.) are properly defined.
 pub async fn fetch_rounds(rustlink: Rustlink) {
-    let contracts = &rustlink.configuration.contracts;
+    let rustlink = &rustlink;
+    let configuration = &rustlink.configuration;
     let mut shutdown_future = rustlink.termination_recv.recv().fuse();
-    let worker_future = workflow_rs::core::task::interval(Duration::from_secs(
-        rustlink.configuration.fetch_interval_seconds,
-    ));
+
+    // Resolve the address and load the metadata of every feed once,
+    // failed feeds are retried on their first fetch.
+    stream::iter(&configuration.contracts)
+        .for_each_concurrent(configuration.max_concurrent_fetches, |feed| async move {
+            let metadata = match feed_address(rustlink, feed).await {
+                Ok(address) => refresh_feed_metadata(rustlink, &feed.identifier, address).await,
+                Err(error) => Err(error),
+            };
+            if let Err(error) = metadata {
+                log::error!("Failed loading metadata of {}: {}", feed.identifier, error);
+            }
+        })
+        .await;
+
+    // Every group of feeds sharing an interval gets its own timer, so each feed is
+    // refreshed at its own interval regardless of the other feeds.
+    let ticks = timers(schedules(configuration));
+
+    // This runs indefinitely, with at most `max_concurrent_fetches` fetches in flight at once.
+    // Every provider of a quorum is queried on its own, and logs are read per aggregator,
+    // so feeds are not batched then.
+    let multicall = configuration
+        .multicall
+        .filter(|_| configuration.quorum.is_none() && !configuration.event_logs);
+    if configuration.multicall.is_some() && multicall.is_none() {
+        log::warn!(
+            "Multicall is not used with a quorum or event logs, feeds are fetched one by one"
+        );
+    }
+    // A feed whose previous fetch is still running, e.g. because the call timeout is longer
+    // than its interval, is skipped rather than fetched twice at once.
+    let in_flight = &InFlight::default();
+    let worker_future = match (&configuration.websocket, multicall) {
+        // Feeds are only refreshed when they emit logs, so the fetch timers are not needed. A feed
+        // that stopped reporting rounds emits no logs either, so its stored round is checked
+        // for staleness at its heartbeat instead. The checks are boxed, as the compiler cannot
+        // tell that the worker is `Send` otherwise.
+        (Some(ws_url), _) => {
+            let staleness_checks = timers(staleness_schedules(configuration))
+                .flat_map(stream::iter)
+                .for_each_concurrent(configuration.max_concurrent_fetches, |feed| {
+                    check_stored_staleness(rustlink, feed)
+                })
+                .boxed();
+            future::join(follow_logs(rustlink, ws_url), staleness_checks)
+                .map(|_| ())
+                .left_future()
+        }
+        (None, Some(multicall_address)) => ticks
+            .for_each_concurrent(configuration.max_concurrent_fetches, move |feeds| {
+                let feeds: Vec<&Feed> = feeds
+                    .into_iter()
+                    .filter(|feed| in_flight.start(feed))
+                    .collect();
+                async move {
+                    fetch_feeds_batched(rustlink, multicall_address, feeds.clone()).await;
+                    feeds.iter().for_each(|feed| in_flight.finish(feed));
+                }
+            })
+            .left_future()
+            .right_future(),
+        (None, None) => ticks
+            .flat_map(stream::iter)
+            .for_each_concurrent(configuration.max_concurrent_fetches, |feed| async move {
+                if in_flight.start(feed) {
+                    fetch_feed(rustlink, feed).await;
+                    in_flight.finish(feed);
+                }
+            })
+            .right_future()
+            .right_future(),
+    }
+    .fuse();
     futures::pin_mut!(worker_future);
 
-    // This loop runs indefinitely, fetching price data.
-    loop {
-        for contract_configuration in contracts {
-            select! {
-                    _ = shutdown_future => {
-                        rustlink.shutdown_send.send(()).await.unwrap();
-                        return;
-                    },
-                    _ = worker_future.next().fuse() => {
-
-                let identifier = &contract_configuration.0; // This variable wasn't used in your original code.
-                let address = &contract_configuration.1;
-
-                // Fetch price data and attempt to send it via the channel.
-                match fetch_round_data_for_contract(&rustlink.configuration, identifier, *address).await
-                {
-                    Ok(price_data) => {
-                        match rustlink.reflector {
-                            Sender(ref sender) => {
-                                // Attempt to send the PriceData through the channel.
-                                if let Err(error) = sender.send(price_data).await {
-                                    log::error!("Failed sending data: {}", error);
-                                }
-                            }
-                        }
-                    }
+    // Feeds given by an ENS name follow it, this runs indefinitely as well.
+    let ens_future = refresh_ens_names(rustlink).fuse();
+    futures::pin_mut!(ens_future);
+
+    select! {
+        _ = shutdown_future => {},
+        _ = ens_future => {},
+        // The worker only finishes early if there are no feeds to fetch.
+        _ = worker_future => {
+            let _ = shutdown_future.await;
+        },
+    }
+    // The instance holds the receiver, so the channel is never closed.
+    let _ = rustlink.shutdown_send.send(()).await;
+}
+
+/// Refreshes a single feed
+pub async fn fetch_feed(rustlink: &Rustlink, feed: &Feed) {
+    let address = match feed_address(rustlink, feed).await {
+        Ok(address) => address,
+        Err(error) => {
+            let attempts = error.attempts();
+            return reflect_failure(rustlink, &feed.identifier, (&error).into(), attempts).await;
+        }
+    };
+    // Registries do not log the rounds they forward, so their feeds are always polled.
+    if rustlink.configuration.event_logs && feed.registry.is_none() {
+        return fetch_feed_from_logs(rustlink, feed, address).await;
+    }
+    if let Some(ref quorum) = rustlink.configuration.quorum {
+        return fetch_feed_with_quorum(rustlink, quorum, feed, address).await;
+    }
+
+    // Fetch price data and attempt to send it via the channel.
+    match fetch_round_data_for_contract(rustlink, feed, address).await {
+        Ok(price_data) => reflect(rustlink, price_data).await,
+        Err(error) => {
+            let attempts = error.attempts();
+            reflect_failure(rustlink, &feed.identifier, (&error).into(), attempts).await
+        }
+    }
+}
+
+/// Refreshes a single feed through every provider of the quorum, and only reflects its
+/// round if enough providers agree on it
+async fn fetch_feed_with_quorum(
+    rustlink: &Rustlink,
+    quorum: &Quorum,
+    feed: &Feed,
+    address: Address,
+) {
+    let metadata = match feed_metadata(rustlink, &feed.identifier, address).await {
+        Ok(metadata) => metadata,
+        Err(error) => {
+            let attempts = error.attempts();
+            return reflect_failure(rustlink, &feed.identifier, (&error).into(), attempts).await;
+        }
+    };
+
+    let calls = quorum
+        .providers
+        .iter()
+        .map(|provider| latest_round(rustlink, provider, feed, address, metadata.decimals));
+    let mut rounds = Vec::new();
+    let mut errors = Vec::new();
+    for result in join_all(calls).await {
+        match result {
+            Ok(round) => rounds.push(round),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    if let Some(round) = quorum.agreed_round(&rounds) {
+        let round = round.clone();
+        match refresh_on_new_phase(rustlink, &feed.identifier, address, &metadata, round).await {
+            Ok(round) => reflect(rustlink, round).await,
+            Err(error) => {
+                let attempts = error.attempts();
+                reflect_failure(rustlink, &feed.identifier, (&error).into(), attempts).await
+            }
+        }
+        return;
+    }
+
+    if rounds.is_empty() {
+        if let Some(error) = errors.first() {
+            let attempts = error.attempts();
+            reflect_failure(rustlink, &feed.identifier, error.into(), attempts).await;
+        }
+        return;
+    }
+
+    log::warn!(
+        "Providers disagree on the latest round of {}, {} of {} failed",
+        feed.identifier,
+        errors.len(),
+        quorum.providers.len()
+    );
+    let event = Event::Disagreement {
+        identifier: feed.identifier.clone(),
+        rounds,
+        failures: errors.len(),
+    };
+    dispatch(rustlink, event).await;
+}
+
+/// Refreshes a single feed from the logs of its aggregator, reflecting every round
+/// reported since the last block that was read
+async fn fetch_feed_from_logs(rustlink: &Rustlink, feed: &Feed, address: Address) {
+    let cursor = rustlink
+        .log_cursors
+        .read()
+        .await
+        .get(&feed.identifier)
+        .copied();
+    let result = match cursor {
+        Some(cursor) => fetch_logged_rounds(rustlink, feed, address, cursor).await,
+        None => fetch_latest_round_and_block(rustlink, feed, address).await,
+    };
+    match result {
+        Ok((rounds, cursor)) => {
+            reflect_caught_up(rustlink, feed, rounds).await;
+            rustlink
+                .log_cursors
+                .write()
+                .await
+                .insert(feed.identifier.clone(), cursor);
+        }
+        Err(error) => {
+            let attempts = error.attempts();
+            reflect_failure(rustlink, &feed.identifier, (&error).into(), attempts).await
+        }
+    }
+}
+
+/// Retrieves the latest round of a feed and the block from which on its logs are to be read.
+/// The block is read first, so a round reported in between is read twice rather than missed.
+async fn fetch_latest_round_and_block<'a>(
+    rustlink: &'a Rustlink,
+    feed: &'a Feed,
+    address: Address,
+) -> Result<(Vec<Round>, LogCursor), ContractCallError<&'a Provider<Failover>>> {
+    let contract = cached_contract(rustlink, &feed.identifier, address).await?;
+    let last_block = contract.block_number().await?;
+    let round = fetch_round_data_for_contract(rustlink, feed, address).await?;
+    let cursor = LogCursor {
+        block: last_block,
+        verified_block: last_block,
+    };
+    Ok((vec![round], cursor))
+}
+
+/// Retrieves the rounds logged by the aggregator of a feed after the block of `cursor`, together
+/// with the cursor to continue from. At most `MAX_LOG_BLOCK_RANGE` blocks are read at once.
+///
+/// The aggregator is only checked against the proxy once the cursor has caught up with the latest
+/// block, as it may have been replaced after the blocks that are being caught up on. If it has
+/// been, the old aggregator has been read up to the latest block, and the new one is read from
+/// the last block at which the old one was still in place.
+async fn fetch_logged_rounds<'a>(
+    rustlink: &'a Rustlink,
+    feed: &'a Feed,
+    address: Address,
+    cursor: LogCursor,
+) -> Result<(Vec<Round>, LogCursor), ContractCallError<&'a Provider<Failover>>> {
+    let metadata = feed_metadata(rustlink, &feed.identifier, address).await?;
+    let contract = cached_contract(rustlink, &feed.identifier, address).await?;
+    let latest_block = contract.block_number().await?;
+    if latest_block <= cursor.block {
+        return Ok((Vec::new(), cursor));
+    }
+    let from_block = cursor.block + 1;
+    let to_block = latest_block.min(cursor.block + MAX_LOG_BLOCK_RANGE);
+    // Feeds that are not behind a proxy log their rounds themselves, without phases.
+    let rounds = contract
+        .logged_rounds(
+            metadata.aggregator_or(address),
+            metadata.phase_id.unwrap_or(0),
+            from_block,
+            to_block,
+        )
+        .await?;
+
+    let Some(phase_id) = metadata.phase_id.filter(|_| to_block == latest_block) else {
+        let cursor = LogCursor {
+            block: to_block,
+            ..cursor
+        };
+        return Ok((rounds, cursor));
+    };
+    if contract.phase_id().await? == phase_id {
+        let cursor = LogCursor {
+            block: to_block,
+            verified_block: to_block,
+        };
+        return Ok((rounds, cursor));
+    }
+
+    // Once the proxy has moved to a new aggregator, the rounds are logged by that one instead.
+    refresh_feed_metadata(rustlink, &feed.identifier, address).await?;
+    let cursor = LogCursor {
+        block: cursor.verified_block,
+        ..cursor
+    };
+    Ok((rounds, cursor))
+}
+
+/// Refreshes the given feeds at once with a single call to the multicall contract
+async fn fetch_feeds_batched(rustlink: &Rustlink, multicall_address: Address, feeds: Vec<&Feed>) {
+    // Feeds read through a registry have no proxy to batch the calls of.
+    let (registry_feeds, feeds): (Vec<&Feed>, Vec<&Feed>) =
+        feeds.into_iter().partition(|feed| feed.registry.is_some());
+    for feed in registry_feeds {
+        fetch_feed(rustlink, feed).await;
+    }
+    match fetch_latest_rounds(rustlink, multicall_address, &feeds).await {
+        Ok(rounds) => {
+            for (identifier, round) in rounds {
+                match round {
+                    Ok(round) => reflect(rustlink, round).await,
                     Err(error) => {
-                        log::error!("Failed updating price: {}", error);
+                        let attempts = error.attempts();
+                        reflect_failure(rustlink, identifier, (&error).into(), attempts).await
                     }
                 }
             }
+        }
+        // The whole batch failed, so every feed in it did.
+        Err(error) => {
+            let attempts = error.attempts();
+            let error = FetchError::from(&error);
+            for feed in feeds {
+                reflect_failure(rustlink, &feed.identifier, error.clone(), attempts).await;
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use async_std::channel::unbounded;
+    use ethers::{
+        abi::{encode, Token},
+        types::{Address, I256, U256},
+        utils::{hex, id},
+    };
+    use futures::StreamExt;
+    use workflow_rs::core::time::unixtime_as_millis_u64;
+
+    use crate::core::{
+        BackfillRange, Error, Event, FetchError, Reflector, Round, Rustlink, SubscriptionFilter,
+        FEED_REGISTRY_ADDRESS,
+    };
+    use crate::fetcher::{
+        check_staleness, fetch_feed, reflect, reflect_caught_up, reflect_failure, schedules,
+        staleness_schedules, InFlight,
+    };
+    use crate::test_utils::{contract, round, rustlink, rustlink_with, serve, ETH, INCH};
+    use crate::websocket::feeds_by_address;
+
+    /// Serves the calls of a Feed Registry with an `ETH/USD` feed on aggregator `0x11..11`
+    /// in phase 3, returns its url
+    async fn serve_registry() -> String {
+        let results = [
+            (
+                "decimals(address,address)",
+                encode(&[Token::Uint(8.into())]),
+            ),
+            (
+                "getFeed(address,address)",
+                encode(&[Token::Address(Address::repeat_byte(0x11))]),
+            ),
+            ("phaseId(address,address)", encode(&[Token::Uint(3.into())])),
+            (
+                "latestRoundData(address,address)",
+                encode(&[
+                    Token::Uint(((3u128 << 64) | 42).into()),
+                    Token::Int(I256::from(301245000000i64).into_raw()),
+                    Token::Uint(1000.into()),
+                    Token::Uint(1010.into()),
+                    Token::Uint(((3u128 << 64) | 42).into()),
+                ]),
+            ),
+        ]
+        .map(|(signature, result)| (hex::encode(&id(signature)[..]), hex::encode(result)));
+        serve(move |_, call| {
+            let data = call["data"].as_str().or(call["input"].as_str());
+            let selector = data.unwrap_or_default().trim_start_matches("0x");
+            results
+                .iter()
+                .find(|(signature, _)| selector.starts_with(signature.as_str()))
+                .map(|(_, result)| format!("0x{}", result))
+        })
+        .await
+    }
+
+    #[test]
+    fn feeds_in_flight_skipped() {
+        let (rustlink, _receiver) = rustlink(vec![contract(ETH)]);
+        let feed = &rustlink.configuration.contracts[0];
+
+        let in_flight = InFlight::default();
+        assert!(in_flight.start(feed));
+        assert!(!in_flight.start(feed));
+        in_flight.finish(feed);
+        assert!(in_flight.start(feed));
+    }
+
+    #[test]
+    fn feeds_grouped_by_interval() {
+        let contracts = vec![
+            (
+                "ETH".to_string(),
+                "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e".to_string(),
+                None,
+            ),
+            (
+                "1INCH".to_string(),
+                "0x9a177Bb9f5b6083E962f9e62bD21d4b5660Aeb03".to_string(),
+                None,
+            ),
+            (
+                "USDT".to_string(),
+                "0xB97Ad0E74fa7d920791E90258A6E2085088b4320".to_string(),
+                Some(60),
+            ),
+        ];
+        let (rustlink, _receiver) = rustlink(contracts.clone());
+
+        let schedules = schedules(&rustlink.configuration);
+        let intervals: Vec<(u64, Vec<&str>)> = schedules
+            .iter()
+            .map(|(seconds, feeds)| {
+                let identifiers = feeds.iter().map(|feed| feed.identifier.as_str());
+                (*seconds, identifiers.collect())
+            })
+            .collect();
+        assert_eq!(
+            intervals,
+            vec![(1, vec!["ETH", "1INCH"]), (60, vec!["USDT"])]
+        );
+        assert!(rustlink.clone().with_fetch_interval("BTC", 60).is_err());
+
+        // Without logs, feeds are checked for staleness at their heartbeat instead.
+        let rustlink = rustlink.with_heartbeat("ETH", 3600).unwrap();
+        let rustlink = rustlink.with_heartbeat("USDT", 0).unwrap();
+        let schedules = staleness_schedules(&rustlink.configuration);
+        let intervals: Vec<(u64, Vec<&str>)> = schedules
+            .iter()
+            .map(|(seconds, feeds)| {
+                let identifiers = feeds.iter().map(|feed| feed.identifier.as_str());
+                (*seconds, identifiers.collect())
+            })
+            .collect();
+        assert_eq!(
+            intervals,
+            vec![(3600, vec!["ETH"]), (1, vec!["1INCH", "USDT"])]
+        );
+
+        let mut contracts = contracts;
+        contracts[0].2 = Some(0);
+        let (sender, _receiver) = unbounded();
+        let zero_interval = Rustlink::try_new(
+            "https://bsc-dataseed1.binance.org/",
+            1,
+            Reflector::Sender(sender),
+            contracts,
+            std::time::Duration::from_secs(10),
+        );
+        assert!(matches!(zero_interval, Err(Error::ZeroInterval)));
+    }
+
+    #[tokio::test]
+    async fn stale_rounds_detected() {
+        let (stale_sender, stale_receiver) = unbounded();
+        let (rustlink, _receiver) = rustlink(vec![contract(ETH)]);
+        let rustlink = rustlink
+            .with_heartbeat("ETH", 3600)
+            .unwrap()
+            .with_stale_sender(stale_sender);
+
+        let now = unixtime_as_millis_u64() / 1000;
+        let mut round = Round {
+            started_at: U256::from(now - 60),
+            updated_at: U256::from(now - 60),
+            ..round("ETH", 1)
+        };
+        let feed = &rustlink.configuration.contracts[0];
+        check_staleness(&rustlink, feed, &mut round).await;
+        assert!(!round.stale);
+        assert!(stale_receiver.is_empty());
+
+        round.updated_at = U256::from(now - 7200);
+        check_staleness(&rustlink, feed, &mut round).await;
+        assert!(round.stale);
+        let stale_feed = stale_receiver.recv().await.unwrap();
+        assert_eq!(stale_feed.identifier, "ETH");
+        assert!(stale_feed.age_seconds >= 7200);
+
+        // Rounds that were caught up on were only replaced later, the newest one is checked.
+        let caught_up = [7200, 3 * 7200].map(|age| Round {
+            updated_at: U256::from(now - age),
+            stale: false,
+            ..round.clone()
+        });
+        reflect_caught_up(&rustlink, feed, caught_up.into_iter().rev().collect()).await;
+        assert_eq!(stale_receiver.len(), 1);
+        assert_eq!(
+            stale_receiver.recv().await.unwrap().round.updated_at,
+            U256::from(now - 7200)
+        );
+        assert!(rustlink.latest("ETH").await.unwrap().stale);
+    }
+
+    #[tokio::test]
+    async fn silent_feeds_detected_stale() {
+        let (stale_sender, stale_receiver) = unbounded();
+        let (rustlink, _receiver) = rustlink(vec![contract(ETH)]);
+        let rustlink = rustlink
+            .with_heartbeat("ETH", 3600)
+            .unwrap()
+            .with_stale_sender(stale_sender);
+        let feed = &rustlink.configuration.contracts[0];
+
+        // No round was caught up on, the stored one went stale in the meantime.
+        let now = unixtime_as_millis_u64() / 1000;
+        let stored = Round {
+            updated_at: U256::from(now - 7200),
+            ..round("ETH", 1)
+        };
+        rustlink.store.update(stored).await;
+        reflect_caught_up(&rustlink, feed, Vec::new()).await;
+        let stale_feed = stale_receiver.recv().await.unwrap();
+        assert_eq!(stale_feed.round.round_id, 1);
+        assert!(stale_feed.age_seconds >= 7200);
+        assert!(rustlink.latest("ETH").await.unwrap().stale);
+
+        let stored = Round {
+            updated_at: U256::from(now - 60),
+            ..round("ETH", 2)
+        };
+        rustlink.store.update(stored).await;
+        reflect_caught_up(&rustlink, feed, Vec::new()).await;
+        assert!(stale_receiver.is_empty());
+        assert!(!rustlink.latest("ETH").await.unwrap().stale);
+    }
+
+    #[tokio::test]
+    async fn panicking_callback_isolated() {
+        let contracts = vec![(
+            "ETH".to_string(),
+            "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e".to_string(),
+        )];
+        let (sender, receiver) = unbounded();
+        let reflector = Reflector::async_callback(move |event: Event| {
+            let sender = sender.clone();
+            async move {
+                if event.round().unwrap().round_id == 1 {
+                    panic!("Callback failed");
+                }
+                sender.send(event).await.unwrap();
+            }
+        });
+        let rustlink = Rustlink::try_new(
+            "https://bsc-dataseed1.binance.org/",
+            1,
+            reflector,
+            contracts,
+            std::time::Duration::from_secs(10),
+        )
+        .unwrap();
+
+        let mut round = round("ETH", 1);
+        reflect(&rustlink, round.clone()).await;
+
+        round.round_id = 2;
+        reflect(&rustlink, round).await;
+        assert_eq!(receiver.recv().await.unwrap().round().unwrap().round_id, 2);
+    }
+
+    #[tokio::test]
+    async fn subscription_receives_filtered_rounds() {
+        let (rustlink, _receiver) = rustlink(vec![contract(ETH), contract(INCH)]);
+        let rounds = rustlink.subscribe(SubscriptionFilter::identifiers(["1INCH"]));
+        let events = rustlink.subscribe_events(SubscriptionFilter::identifiers(["1INCH"]));
+        futures::pin_mut!(rounds, events);
+
+        for identifier in ["ETH", "1INCH"] {
+            let round = round(identifier, 1);
+            reflect(&rustlink, round).await;
+        }
+        reflect_failure(&rustlink, "1INCH", FetchError::Timeout, 1).await;
+        let round = round("1INCH", 2);
+        reflect(&rustlink, round).await;
+
+        // Failures are only passed on to the subscribers of events
+        assert_eq!(rounds.next().await.unwrap().round_id, 1);
+        assert_eq!(rounds.next().await.unwrap().round_id, 2);
+        assert!(matches!(events.next().await, Some(Event::Round(round)) if round.round_id == 1));
+        assert!(matches!(events.next().await, Some(Event::Failed { .. })));
+    }
+
+    #[tokio::test]
+    async fn failures_reflected() {
+        let (rustlink, receiver) = rustlink(vec![contract(ETH)]);
+
+        reflect_failure(&rustlink, "ETH", FetchError::Timeout, 3).await;
+        match receiver.recv().await.unwrap() {
+            Event::Failed {
+                identifier,
+                error,
+                attempts,
+            } => {
+                assert_eq!(identifier, "ETH");
+                assert_eq!(error, FetchError::Timeout);
+                assert_eq!(attempts, 3);
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        assert!(rustlink.latest("ETH").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn registry_feeds_read_through_registry() {
+        let contracts = Vec::<(String, String)>::new();
+        let (rustlink, receiver) = rustlink_with(&serve_registry().await, contracts);
+        let rustlink = rustlink
+            .with_registry_feeds(FEED_REGISTRY_ADDRESS, [("ETH", "USD")])
+            .await
+            .unwrap();
+
+        let feed = &rustlink.configuration.contracts[0];
+        assert_eq!(feed.identifier, "ETH/USD");
+        fetch_feed(&rustlink, feed).await;
+        match receiver.recv().await.unwrap() {
+            Event::Round(round) => {
+                assert_eq!(round.identifier, "ETH/USD");
+                assert_eq!(round.phase_id(), 3);
+                assert_eq!(round.aggregator_round_id(), 42);
+                assert_eq!(round.price().to_string(), "3012.45000000");
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+
+        let metadata = rustlink.metadata("ETH/USD").await.unwrap();
+        assert_eq!(metadata.decimals, 8);
+        assert_eq!(metadata.aggregator, Some(Address::repeat_byte(0x11)));
+        assert_eq!(metadata.phase_id, Some(3));
+
+        // The registry has no proxy to follow or walk the history of.
+        let followed = feeds_by_address(&rustlink).await;
+        assert_eq!(
+            followed.keys().collect::<Vec<_>>(),
+            [&Address::repeat_byte(0x11)]
+        );
+        let not_walkable = Some(Error::RegistryFeed("ETH/USD".to_string()));
+        assert_eq!(rustlink.fetch_round("ETH/USD", 1).await.err(), not_walkable);
+        assert_eq!(rustlink.round_at("ETH/USD", 1000).await.err(), not_walkable);
+        assert!(matches!(
+            rustlink
+                .backfill("ETH/USD", BackfillRange::RoundIds { from: 1, to: 2 })
+                .await,
+            Err(Error::RegistryFeed(_))
+        ));
+    }
+}
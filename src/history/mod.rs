@@ -0,0 +1,264 @@
+use ethers::{providers::Provider, types::U256};
+use futures::{stream, Stream};
+
+use crate::failover::Failover;
+use crate::interface::{ChainlinkContract, ContractCallError, Round, PHASE_OFFSET};
+
+/// The range of historical rounds to retrieve during a backfill.
+/// Both bounds are inclusive.
+#[derive(Clone, Copy, Debug)]
+pub enum BackfillRange {
+    /// All rounds that were updated between two unix timestamps (in seconds)
+    Timestamps { from: u64, to: u64 },
+    /// All rounds between two proxy round ids
+    RoundIds { from: u128, to: u128 },
+}
+
+/// Position of the backfill cursor within the phases of a proxy
+#[derive(Clone, Copy)]
+struct Cursor {
+    phase_id: u16,
+    aggregator_round_id: u64,
+}
+
+impl Cursor {
+    fn from_round_id(round_id: u128) -> Self {
+        Cursor {
+            phase_id: (round_id >> PHASE_OFFSET) as u16,
+            aggregator_round_id: round_id as u64,
+        }
+    }
+}
+
+/// Retrieves a round from the proxy, returning `None` for rounds that are missing.
+/// Rounds at the start of a phase may be missing on the proxy, and rounds that
+/// were never updated are gaps as well.
+async fn present_round<'a>(
+    contract: &ChainlinkContract<'a>,
+    round_id: u128,
+) -> Result<Option<Round>, ContractCallError<&'a Provider<Failover>>> {
+    match contract.round_data_at(round_id).await {
+        Ok(round) if round.updated_at.is_zero() => Ok(None),
+        Ok(round) => Ok(Some(round)),
+        Err(ContractCallError::Contract(error)) if error.is_revert() => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+struct Backfill<'a> {
+    contract: ChainlinkContract<'a>,
+    range: BackfillRange,
+    cursor: Option<Cursor>,
+}
+
+impl<'a> Backfill<'a> {
+    /// Walks one step backwards and returns the next round within the range.
+    /// Returns `None` once the lower bound of the range or the first phase has been passed.
+    async fn next_round(
+        &mut self,
+    ) -> Option<Result<Round, ContractCallError<&'a Provider<Failover>>>> {
+        loop {
+            let cursor = self.cursor?;
+
+            // The current phase is exhausted, continue from the last round of the previous one.
+            if cursor.aggregator_round_id == 0 {
+                if cursor.phase_id <= 1 {
+                    self.cursor = None;
+                    return None;
+                }
+                let phase_id = cursor.phase_id - 1;
+                match self.contract.latest_aggregator_round_id(phase_id).await {
+                    Ok(aggregator_round_id) => {
+                        self.cursor = Some(Cursor {
+                            phase_id,
+                            aggregator_round_id,
+                        })
+                    }
+                    // The cursor stays put, so the hop is retried on the next poll.
+                    Err(error) => return Some(Err(error)),
+                }
+                continue;
+            }
+
+            let round_id = Round::compose_round_id(cursor.phase_id, cursor.aggregator_round_id);
+            self.cursor = Some(Cursor {
+                aggregator_round_id: cursor.aggregator_round_id - 1,
+                ..cursor
+            });
+
+            if let BackfillRange::RoundIds { from, .. } = self.range {
+                if round_id < from {
+                    self.cursor = None;
+                    return None;
+                }
+            }
+
+            let round = match present_round(&self.contract, round_id).await {
+                Ok(Some(round)) => round,
+                Ok(None) => continue,
+                Err(error) => return Some(Err(error)),
+            };
+
+            if let BackfillRange::Timestamps { from, to } = self.range {
+                let updated_at = round.updated_at.as_u64();
+                if updated_at > to {
+                    continue;
+                }
+                if updated_at < from {
+                    self.cursor = None;
+                    return None;
+                }
+            }
+
+            return Some(Ok(round));
+        }
+    }
+}
+
+/// Streams every historical round of a contract within the given range, starting with
+/// the most recent one and walking backwards across the phases of the proxy.
+///
+/// Rounds that are missing on the proxy, such as the ones where a new phase starts,
+/// are skipped. Failing calls are yielded as errors and the walk continues with the
+/// round before it. A failing hop to the previous phase is yielded as an error as well,
+/// and is retried when the stream is polled again.
+///
+/// For a range of round ids, the walk starts at `to` or at the latest round, whichever is
+/// lower. For a range of timestamps, it starts at the round in effect at `to`, which is
+/// binary searched like in `round_at` rather than walked to from the latest round.
+pub async fn backfill<'a>(
+    contract: ChainlinkContract<'a>,
+    range: BackfillRange,
+) -> Result<
+    impl Stream<Item = Result<Round, ContractCallError<&'a Provider<Failover>>>> + 'a,
+    ContractCallError<&'a Provider<Failover>>,
+> {
+    let start = match range {
+        // Round ids after the latest round do not exist yet, so they are not walked.
+        BackfillRange::RoundIds { to, .. } => {
+            Some(to.min(contract.latest_round_data().await?.round_id))
+        }
+        BackfillRange::Timestamps { to, .. } => round_at(&contract, to)
+            .await?
+            .map(|round_at| round_at.round.round_id),
+    };
+    let backfill = Backfill {
+        contract,
+        range,
+        cursor: start.map(Cursor::from_round_id),
+    };
+
+    Ok(stream::unfold(backfill, |mut backfill| async move {
+        let round = backfill.next_round().await?;
+        Some((round, backfill))
+    }))
+}
+
+/// The round that was in effect at a given point in time.
+#[derive(Clone, Debug)]
+pub struct RoundAt {
+    /// The latest round that was updated at or before the requested timestamp
+    pub round: Round,
+    /// The round that replaced it, or `None` if `round` is still the latest one.
+    /// Its `updated_at` marks the end of the period in which `round` was valid.
+    pub next: Option<Round>,
+}
+
+/// Finds the latest round of a contract with `updated_at <= timestamp`, together with
+/// the round that followed it.
+///
+/// Every phase of the proxy is binary searched from the most recent one backwards.
+/// Missing rounds are assumed to only occur at the start of a phase. Returns `None`
+/// if the feed has no round at or before `timestamp`.
+pub async fn round_at<'a>(
+    contract: &ChainlinkContract<'a>,
+    timestamp: u64,
+) -> Result<Option<RoundAt>, ContractCallError<&'a Provider<Failover>>> {
+    let latest = contract.latest_round_data().await?;
+    if latest.updated_at <= U256::from(timestamp) {
+        return Ok(Some(RoundAt {
+            round: latest,
+            next: None,
+        }));
+    }
+
+    let mut phase_id = latest.phase_id();
+    let mut last_aggregator_round_id = latest.aggregator_round_id();
+    let mut next = Some(latest);
+
+    loop {
+        // Rounds below `low` are missing or at/before the timestamp, rounds above `high` are after it.
+        let (mut low, mut high) = (1u64, last_aggregator_round_id);
+        let mut found = None;
+        while low <= high {
+            let middle = low + (high - low) / 2;
+            let round_id = Round::compose_round_id(phase_id, middle);
+            match present_round(contract, round_id).await? {
+                Some(round) if round.updated_at > U256::from(timestamp) => {
+                    next = Some(round);
+                    high = middle - 1;
+                }
+                round => {
+                    found = round.or(found);
+                    low = middle + 1;
+                }
+            }
+        }
+
+        if let Some(round) = found {
+            return Ok(Some(RoundAt { round, next }));
+        }
+
+        if phase_id <= 1 {
+            return Ok(None);
+        }
+        phase_id -= 1;
+        last_aggregator_round_id = contract.latest_aggregator_round_id(phase_id).await?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use crate::{
+        failover::Failover,
+        history::round_at,
+        interface::{ChainlinkContract, FeedMetadata},
+        retry::RetryPolicy,
+    };
+    use ethers::{abi::Address, providers::Provider};
+
+    #[tokio::test]
+    async fn round_at_previous_update() {
+        let failover = Failover::new(
+            ["https://bsc-dataseed1.binance.org/"],
+            Duration::from_secs(10),
+        );
+        let provider = Provider::new(failover.unwrap());
+
+        let address = "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e"
+            .parse::<Address>()
+            .unwrap();
+        let timeout = Duration::from_secs(10);
+        let metadata = FeedMetadata::load(&provider, address, timeout, RetryPolicy::none())
+            .await
+            .unwrap();
+        let chainlink_contract =
+            ChainlinkContract::with_decimals(&provider, "ETH", address, metadata.decimals, timeout);
+        let latest = chainlink_contract.latest_round_data().await.unwrap();
+        let previous = chainlink_contract
+            .round_data_at(latest.round_id - 1)
+            .await
+            .unwrap();
+
+        let round_at = round_at(&chainlink_contract, previous.updated_at.as_u64())
+            .await
+            .unwrap()
+            .unwrap();
+        println!("Received data: {:#?}", round_at);
+        assert_eq!(round_at.round.round_id, previous.round_id);
+        assert_eq!(round_at.next.unwrap().round_id, latest.round_id);
+    }
+}
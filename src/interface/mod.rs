@@ -1,20 +1,30 @@
 use async_std::future::{timeout, TimeoutError};
 use ethers::{
-    abi::{Abi, AbiError},
-    contract::{Contract, ContractError},
-    providers::{Http, Middleware, Provider},
-    types::{Address, U256},
+    abi::{self, Abi, AbiError, Detokenize, InvalidOutputType, RawLog, Token, Tokenizable},
+    contract::{Contract, ContractCall, ContractError, MulticallError},
+    providers::{Middleware, Provider},
+    types::{Address, Bytes, Filter, Log, I256, U256},
 };
 use serde::{Deserialize, Serialize};
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 use thiserror::Error;
 
+use crate::event::FetchError;
+use crate::failover::Failover;
+use crate::price::Price;
+use crate::retry::{retry, RetryPolicy};
+
 #[derive(Clone)]
 pub struct ChainlinkContract<'a> {
-    pub contract: Contract<&'a Provider<Http>>,
+    pub contract: Contract<&'a Provider<Failover>>,
     pub identifier: &'a str,
     pub decimals: u8,
     pub call_timeout: Duration,
+    pub retry: RetryPolicy,
 }
 
 #[derive(Error, Debug)]
@@ -25,6 +35,46 @@ pub enum ContractCallError<T: Middleware> {
     Timeout(#[from] TimeoutError),
     #[error("Contract error: {0}")]
     Contract(#[from] ContractError<T>),
+    #[error("Multicall error: {0}")]
+    Multicall(#[from] MulticallError<T>),
+    #[error("Invalid output: {0}")]
+    InvalidOutput(#[from] InvalidOutputType),
+    #[error("Call reverted: {0}")]
+    Revert(Bytes),
+    #[error("{} after {attempts} attempts", last.to_string())]
+    Retried {
+        attempts: u32,
+        last: Box<ContractCallError<T>>,
+    },
+}
+
+impl<T: Middleware> ContractCallError<T> {
+    /// Number of attempts that were made before the call failed
+    pub fn attempts(&self) -> u32 {
+        match self {
+            ContractCallError::Retried { attempts, .. } => *attempts,
+            _ => 1,
+        }
+    }
+
+    /// Whether the call may succeed when it is retried
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            FetchError::from(self),
+            FetchError::Timeout | FetchError::Transport(_)
+        )
+    }
+
+    /// Records how many attempts were made before the call failed
+    pub(crate) fn with_attempts(self, attempts: u32) -> Self {
+        if attempts <= 1 {
+            return self;
+        }
+        ContractCallError::Retried {
+            attempts,
+            last: Box::new(self),
+        }
+    }
 }
 
 /// The latest price received for this symbol.
@@ -41,46 +91,185 @@ pub struct Round {
     pub started_at: U256,
     /// Timestamp for when the aggregator posted the price update
     pub updated_at: U256,
-    /// Answer of this round         
-    pub answer: f64,
+    /// Raw signed integer answer of this round, scaled by `10^decimals`.
+    /// Serialized as a decimal string to preserve its sign and precision.
+    #[serde(with = "crate::price::decimal")]
+    pub answer: I256,
+    /// Number of decimals of the answer
+    pub decimals: u8,
+    /// Whether the round was older than the heartbeat of its feed when it was fetched
+    #[serde(default)]
+    pub stale: bool,
+    /// Number of attempts it took to fetch the round
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+/// Static information about a feed that only changes when the proxy
+/// is pointed to a new aggregator.
+///
+/// Only the decimals are required. The other fields are `None` for contracts that revert
+/// the call, e.g. `aggregator` and `phase_id` for an aggregator that is not behind a proxy.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FeedMetadata {
+    /// Number of decimals of the answers
+    pub decimals: u8,
+    /// Description of the feed, e.g. `ETH / USD`
+    pub description: Option<String>,
+    /// Version of the underlying aggregator
+    pub version: Option<U256>,
+    /// Address of the aggregator the proxy currently points to
+    pub aggregator: Option<Address>,
+    /// Phase of the proxy in which the aggregator was set
+    pub phase_id: Option<u16>,
 }
 
+impl FeedMetadata {
+    /// Retrieves the metadata of the feed at `address`, retrying the `decimals` call
+    /// according to `retry`.
+    pub async fn load(
+        provider: &Provider<Failover>,
+        address: Address,
+        call_timeout: Duration,
+        retry_policy: RetryPolicy,
+    ) -> Result<FeedMetadata, ContractCallError<&Provider<Failover>>> {
+        let contract = Contract::new(address, abi().clone(), Arc::new(provider));
+        let (attempts, decimals) = retry(
+            &retry_policy,
+            call_timeout,
+            ContractCallError::is_transient,
+            || async { Ok(decimals(&contract).await?) },
+        )
+        .await;
+        let decimals = decimals.map_err(|error| error.with_attempts(attempts))?;
+
+        Ok(FeedMetadata {
+            decimals,
+            description: optional_call(&contract, "description", call_timeout).await?,
+            version: optional_call(&contract, "version", call_timeout).await?,
+            aggregator: optional_call(&contract, "aggregator", call_timeout).await?,
+            phase_id: optional_call(&contract, "phaseId", call_timeout).await?,
+        })
+    }
+
+    /// The aggregator that reports the rounds of the feed at `address`,
+    /// which is the feed itself if it is not behind a proxy
+    pub fn aggregator_or(&self, address: Address) -> Address {
+        self.aggregator.unwrap_or(address)
+    }
+}
+
+/// Number of bits the phase id is shifted by inside a proxy round id
+pub const PHASE_OFFSET: u32 = 64;
+
+impl Round {
+    /// Builds a proxy round id from a phase id and a round id of the underlying aggregator.
+    ///
+    /// Chainlink proxies report round ids as `phaseId << 64 | aggregatorRoundId`.
+    pub fn compose_round_id(phase_id: u16, aggregator_round_id: u64) -> u128 {
+        ((phase_id as u128) << PHASE_OFFSET) | aggregator_round_id as u128
+    }
+
+    /// Phase of the proxy in which this round was reported. The phase is bumped
+    /// every time the proxy is pointed to a new underlying aggregator.
+    pub fn phase_id(&self) -> u16 {
+        (self.round_id >> PHASE_OFFSET) as u16
+    }
+
+    /// Round id within the underlying aggregator of this round's phase
+    pub fn aggregator_round_id(&self) -> u64 {
+        self.round_id as u64
+    }
+
+    /// The exact answer of this round as a fixed-point price
+    pub fn price(&self) -> Price {
+        Price::new(self.answer, self.decimals)
+    }
+}
+
+/// Type alias for the raw round tuple returned by the contract
+pub type RawRound = (u128, I256, U256, U256, u128);
+
 /// Type alias for the raw round call to the contract
-pub type RoundCall<'a> = Result<(u128, u128, U256, U256, u128), ContractError<&'a Provider<Http>>>;
+pub type RoundCall<'a> = Result<RawRound, ContractError<&'a Provider<Failover>>>;
 
 #[allow(clippy::redundant_allocation)]
 async fn decimals<'a>(
-    contract: &ethers::contract::ContractInstance<Arc<&'a Provider<Http>>, &'a Provider<Http>>,
-) -> Result<u8, ContractError<&'a Provider<Http>>> {
+    contract: &ethers::contract::ContractInstance<
+        Arc<&'a Provider<Failover>>,
+        &'a Provider<Failover>,
+    >,
+) -> Result<u8, ContractError<&'a Provider<Failover>>> {
     Ok(contract
-        .method::<_, U256>("decimals", ())
-        .unwrap()
+        .method::<_, U256>("decimals", ())?
         .call()
         .await?
         .as_u64() as u8)
 }
 
+/// Calls a method without arguments that not every feed has,
+/// returning `None` if the contract reverts the call
+async fn optional_call<'a, D: Detokenize>(
+    contract: &Contract<&'a Provider<Failover>>,
+    method: &str,
+    call_timeout: Duration,
+) -> Result<Option<D>, ContractCallError<&'a Provider<Failover>>> {
+    match timeout(call_timeout, contract.method::<_, D>(method, ())?.call()).await? {
+        Ok(value) => Ok(Some(value)),
+        Err(error) if error.is_revert() => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// The bundled aggregator ABI, parsed only once
+fn abi() -> &'static Abi {
+    static ABI: OnceLock<Abi> = OnceLock::new();
+    ABI.get_or_init(|| serde_json::from_str(include_str!("IAggregatorV3Interface.json")).unwrap())
+}
+
+/// The value of the parameter with the given name of a decoded log
+fn log_param<T: Tokenizable>(log: &abi::Log, name: &str) -> Result<T, AbiError> {
+    let token = log
+        .params
+        .iter()
+        .find(|param| param.name == name)
+        .map(|param| param.value.clone())
+        .ok_or_else(|| InvalidOutputType(format!("Missing log parameter {}", name)))?;
+    Ok(T::from_token(token)?)
+}
+
 impl<'a> ChainlinkContract<'a> {
     /// Creates a new instance of a chainlink price aggregator. This is just a wrapper
     /// function to simplify the interactions with the contract.
-    pub async fn new(
-        provider: &'a Provider<Http>,
+    ///
+    /// The decimals of the feed must be known already, see `FeedMetadata::load` to retrieve them.
+    /// No calls are made to the contract, and failing calls are not retried, see `with_retry`.
+    pub fn with_decimals(
+        provider: &'a Provider<Failover>,
         identifier: &'a str,
         contract_address: Address,
+        decimals: u8,
         call_timeout: Duration,
-    ) -> Result<ChainlinkContract<'a>, ContractCallError<&'a Provider<Http>>> {
-        let abi: Abi = serde_json::from_str(include_str!("IAggregatorV3Interface.json")).unwrap();
-        let contract: ethers::contract::ContractInstance<Arc<&Provider<Http>>, &Provider<Http>> =
-            Contract::new(contract_address, abi, Arc::new(provider));
-
-        let decimals = timeout(call_timeout, decimals(&contract)).await??;
+    ) -> ChainlinkContract<'a> {
+        let contract: ethers::contract::ContractInstance<
+            Arc<&Provider<Failover>>,
+            &Provider<Failover>,
+        > = Contract::new(contract_address, abi().clone(), Arc::new(provider));
 
-        Ok(ChainlinkContract {
+        ChainlinkContract {
             contract,
             decimals,
             identifier,
             call_timeout,
-        })
+            retry: RetryPolicy::none(),
+        }
+    }
+
+    /// Retries the `decimals` and `latestRoundData` calls according to `retry`.
+    /// All attempts of a call together are still bounded by the call timeout.
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
     }
 
     /// Wrapper function to call the latestRoundData method on the contract
@@ -89,27 +278,197 @@ impl<'a> ChainlinkContract<'a> {
         round_call
     }
 
+    /// Wrapper function to call the getRoundData method on the contract
+    async fn historical_round_data(&self, round_id: u128) -> RoundCall<'a> {
+        let round_call: RoundCall = self.contract.method("getRoundData", round_id)?.call().await;
+        round_call
+    }
+
+    /// Retrieves the id of the last round reported by the aggregator that
+    /// was in use by the proxy during the given phase
+    pub async fn latest_aggregator_round_id(
+        &self,
+        phase_id: u16,
+    ) -> Result<u64, ContractCallError<&'a Provider<Failover>>> {
+        let aggregator = timeout(
+            self.call_timeout,
+            self.contract
+                .method::<_, Address>("phaseAggregators", phase_id)?
+                .call(),
+        )
+        .await??;
+
+        // The phase aggregator shares the AggregatorV3 interface with the proxy
+        let latest_round = timeout(
+            self.call_timeout,
+            self.contract
+                .at(aggregator)
+                .method::<_, U256>("latestRound", ())?
+                .call(),
+        )
+        .await??;
+        Ok(latest_round.as_u64())
+    }
+
+    /// Retrieves the phase the proxy is currently in
+    pub async fn phase_id(&self) -> Result<u16, ContractCallError<&'a Provider<Failover>>> {
+        Ok(timeout(
+            self.call_timeout,
+            self.contract.method::<_, u16>("phaseId", ())?.call(),
+        )
+        .await??)
+    }
+
+    /// Retrieves the number of the latest block
+    pub async fn block_number(&self) -> Result<u64, ContractCallError<&'a Provider<Failover>>> {
+        let block_number = timeout(
+            self.call_timeout,
+            self.contract.client_ref().get_block_number(),
+        )
+        .await?
+        .map_err(ContractError::from_middleware_error)?;
+        Ok(block_number.as_u64())
+    }
+
+    /// Retrieves every round reported by `aggregator` from block `from_block` up to and
+    /// including block `to_block`, using its `AnswerUpdated` and `NewRound` logs.
+    ///
+    /// Unlike polling `latestRoundData`, this also captures rounds that were superseded
+    /// before they could be polled. The rounds are reported as rounds of the proxy in
+    /// the given phase, which has to be the phase in which `aggregator` was set.
+    pub async fn logged_rounds(
+        &self,
+        aggregator: Address,
+        phase_id: u16,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<Round>, ContractCallError<&'a Provider<Failover>>> {
+        let answer_updated = abi().event("AnswerUpdated").map_err(AbiError::from)?;
+        let new_round = abi().event("NewRound").map_err(AbiError::from)?;
+        let filter = Filter::new()
+            .address(aggregator)
+            .topic0(vec![answer_updated.signature(), new_round.signature()])
+            .from_block(from_block)
+            .to_block(to_block);
+        let logs = timeout(
+            self.call_timeout,
+            self.contract.client_ref().get_logs(&filter),
+        )
+        .await?
+        .map_err(ContractError::from_middleware_error)?;
+        Ok(self.decode_round_logs(phase_id, &logs)?)
+    }
+
+    /// Decodes the `AnswerUpdated` logs of an aggregator into rounds of the proxy in the given
+    /// phase, ordered by round id. The start of a round is read from its `NewRound` log if it
+    /// is among `logs`, and is otherwise assumed to be its update. Other logs are ignored.
+    pub fn decode_round_logs(&self, phase_id: u16, logs: &[Log]) -> Result<Vec<Round>, AbiError> {
+        let answer_updated = abi().event("AnswerUpdated")?;
+        let new_round = abi().event("NewRound")?;
+
+        let mut started_at: HashMap<U256, U256> = HashMap::new();
+        let mut updates = Vec::new();
+        for log in logs {
+            let raw_log = RawLog {
+                topics: log.topics.clone(),
+                data: log.data.to_vec(),
+            };
+            match log.topics.first() {
+                Some(topic) if *topic == new_round.signature() => {
+                    let log = new_round.parse_log(raw_log)?;
+                    started_at.insert(log_param(&log, "roundId")?, log_param(&log, "startedAt")?);
+                }
+                Some(topic) if *topic == answer_updated.signature() => {
+                    updates.push(answer_updated.parse_log(raw_log)?);
+                }
+                _ => {}
+            }
+        }
+
+        let mut rounds = updates
+            .iter()
+            .map(|log| {
+                let aggregator_round_id: U256 = log_param(log, "roundId")?;
+                let updated_at: U256 = log_param(log, "updatedAt")?;
+                let round_id = Round::compose_round_id(phase_id, aggregator_round_id.as_u64());
+                Ok(Round {
+                    identifier: self.identifier.to_string(),
+                    round_id,
+                    answered_in_round: round_id,
+                    started_at: started_at
+                        .get(&aggregator_round_id)
+                        .copied()
+                        .unwrap_or(updated_at),
+                    updated_at,
+                    answer: log_param(log, "current")?,
+                    decimals: self.decimals,
+                    stale: false,
+                    attempts: 1,
+                })
+            })
+            .collect::<Result<Vec<_>, AbiError>>()?;
+        rounds.sort_by_key(|round| round.round_id);
+        Ok(rounds)
+    }
+
     /// Retrieves the latest price of this underlying asset
     /// from the chainlink decentralized data feed
-    pub async fn latest_round_data(&self) -> Result<Round, ContractCallError<&'a Provider<Http>>> {
-        // Call the contract, but timeout after 10 seconds
-        let (round_id, answer, started_at, updated_at, answered_in_round) =
-            timeout(self.call_timeout, self.round_data()).await??;
+    pub async fn latest_round_data(
+        &self,
+    ) -> Result<Round, ContractCallError<&'a Provider<Failover>>> {
+        // Retry the call, but timeout after the configured call timeout
+        let (attempts, round_call) = retry(
+            &self.retry,
+            self.call_timeout,
+            ContractCallError::is_transient,
+            || async { Ok(self.round_data().await?) },
+        )
+        .await;
+        let round_call = round_call.map_err(|error| error.with_attempts(attempts))?;
+        Ok(Round {
+            attempts,
+            ..self.to_round(round_call)
+        })
+    }
 
-        // Convert the answer on contract to a string.
-        let float_answer: f64 = answer.to_string().parse().unwrap();
+    /// Retrieves the price of this underlying asset at a specific past round
+    /// from the chainlink decentralized data feed
+    pub async fn round_data_at(
+        &self,
+        round_id: u128,
+    ) -> Result<Round, ContractCallError<&'a Provider<Failover>>> {
+        let round_call = timeout(self.call_timeout, self.historical_round_data(round_id)).await??;
+        Ok(self.to_round(round_call))
+    }
 
-        // Convert the contract answer into a human-readable answer
-        let human_answer = float_answer / (10f64.powi(self.decimals.into()));
+    /// Builds the call to the latestRoundData method without sending it,
+    /// so it can be batched together with other calls
+    pub fn latest_round_data_call(
+        &self,
+    ) -> Result<ContractCall<&'a Provider<Failover>, RawRound>, AbiError> {
+        self.contract.method("latestRoundData", ())
+    }
 
-        Ok(Round {
+    /// Decodes the raw output of a round call into a `Round`
+    pub fn decode_round(&self, token: Token) -> Result<Round, InvalidOutputType> {
+        Ok(self.to_round(RawRound::from_token(token)?))
+    }
+
+    /// Converts the raw round tuple returned by the contract into a `Round`
+    fn to_round(&self, round_call: RawRound) -> Round {
+        let (round_id, answer, started_at, updated_at, answered_in_round) = round_call;
+
+        Round {
             identifier: self.identifier.to_string(),
             round_id,
             answered_in_round,
             started_at,
             updated_at,
-            answer: human_answer,
-        })
+            answer,
+            decimals: self.decimals,
+            stale: false,
+            attempts: 1,
+        }
     }
 }
 
@@ -118,25 +477,152 @@ mod tests {
 
     use std::time::Duration;
 
-    use crate::interface::ChainlinkContract;
-    use ethers::{abi::Address, providers::Provider};
+    use crate::failover::Failover;
+    use crate::interface::{abi, ChainlinkContract, FeedMetadata, Round};
+    use crate::retry::RetryPolicy;
+    use crate::test_utils::{round, round_answered};
+    use ethers::{
+        abi::{encode, Address, Token},
+        providers::Provider,
+        types::{Log, H256, I256, U256},
+    };
+
+    fn eth_address() -> Address {
+        "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e"
+            .parse::<Address>()
+            .unwrap()
+    }
+
+    async fn eth_metadata(provider: &Provider<Failover>) -> FeedMetadata {
+        let timeout = Duration::from_secs(10);
+        FeedMetadata::load(provider, eth_address(), timeout, RetryPolicy::none())
+            .await
+            .unwrap()
+    }
+
+    async fn eth_contract(provider: &Provider<Failover>) -> ChainlinkContract<'_> {
+        let decimals = eth_metadata(provider).await.decimals;
+        let timeout = Duration::from_secs(10);
+        ChainlinkContract::with_decimals(provider, "ETH", eth_address(), decimals, timeout)
+    }
 
     #[tokio::test]
     async fn valid_answer() {
-        let provider = Provider::try_from("https://bsc-dataseed1.binance.org/").unwrap();
+        let failover = Failover::new(
+            ["https://bsc-dataseed1.binance.org/"],
+            Duration::from_secs(10),
+        );
+        let provider = Provider::new(failover.unwrap());
+
+        let chainlink_contract = eth_contract(&provider).await;
+        let price_data = chainlink_contract.latest_round_data().await.unwrap();
+        println!("Received data: {:#?}", price_data);
+        assert!(price_data.answer > I256::zero());
+        assert_eq!(price_data.decimals, 8);
+    }
+
+    #[tokio::test]
+    async fn valid_metadata() {
+        let failover = Failover::new(
+            ["https://bsc-dataseed1.binance.org/"],
+            Duration::from_secs(10),
+        );
+        let provider = Provider::new(failover.unwrap());
+
+        let metadata = eth_metadata(&provider).await;
+        println!("Received metadata: {:#?}", metadata);
+        assert_eq!(metadata.decimals, 8);
+        assert_eq!(metadata.description.as_deref(), Some("ETH / USD"));
+        assert!(metadata.phase_id.unwrap() > 0);
+    }
+
+    #[test]
+    fn round_id_decoding() {
+        let round_id = Round::compose_round_id(2, 1337);
+        assert_eq!(round_id, 36893488147419104569);
 
-        let chainlink_contract = ChainlinkContract::new(
+        let round = round("ETH", round_id);
+        assert_eq!(round.phase_id(), 2);
+        assert_eq!(round.aggregator_round_id(), 1337);
+    }
+
+    #[test]
+    fn rounds_decoded_from_logs() {
+        let failover = Failover::new(["http://127.0.0.1:9"], Duration::from_secs(1));
+        let provider = Provider::new(failover.unwrap());
+        let contract = ChainlinkContract::with_decimals(
             &provider,
             "ETH",
-            "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e"
-                .parse::<Address>()
-                .unwrap(),
+            Address::zero(),
+            8,
+            Duration::from_secs(1),
+        );
+
+        let word = |value: U256| H256::from_slice(&encode(&[Token::Uint(value)]));
+        let answer_updated = |answer: i64, round_id: u64, updated_at: u64| Log {
+            topics: vec![
+                abi().event("AnswerUpdated").unwrap().signature(),
+                H256::from_slice(&encode(&[Token::Int(I256::from(answer).into_raw())])),
+                word(U256::from(round_id)),
+            ],
+            data: encode(&[Token::Uint(U256::from(updated_at))]).into(),
+            ..Default::default()
+        };
+        let new_round = Log {
+            topics: vec![
+                abi().event("NewRound").unwrap().signature(),
+                word(U256::from(11)),
+                H256::from(Address::repeat_byte(0x22)),
+            ],
+            data: encode(&[Token::Uint(U256::from(1000))]).into(),
+            ..Default::default()
+        };
+        let logs = [
+            answer_updated(-5, 11, 1010),
+            new_round,
+            answer_updated(300, 10, 990),
+        ];
+
+        let rounds = contract.decode_round_logs(2, &logs).unwrap();
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(rounds[0].round_id, Round::compose_round_id(2, 10));
+        assert_eq!(rounds[0].answer, I256::from(300));
+        assert_eq!(rounds[0].started_at, U256::from(990));
+        assert_eq!(rounds[1].aggregator_round_id(), 11);
+        assert_eq!(rounds[1].phase_id(), 2);
+        assert_eq!(rounds[1].answer, I256::from(-5));
+        assert_eq!(rounds[1].started_at, U256::from(1000));
+        assert_eq!(rounds[1].updated_at, U256::from(1010));
+        assert_eq!(rounds[1].decimals, 8);
+    }
+
+    #[test]
+    fn negative_answer_serialization() {
+        let round = round_answered("RATE", 1, -125000);
+        let json = serde_json::to_value(&round).unwrap();
+        assert_eq!(json["answer"], "-125000");
+
+        let deserialized: Round = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized.answer, round.answer);
+        assert_eq!(deserialized.price().to_string(), "-0.00125000");
+    }
+
+    #[tokio::test]
+    async fn valid_historical_answer() {
+        let failover = Failover::new(
+            ["https://bsc-dataseed1.binance.org/"],
             Duration::from_secs(10),
-        )
-        .await
-        .unwrap();
-        let price_data = chainlink_contract.latest_round_data().await.unwrap();
-        println!("Received data: {:#?}", price_data);
-        assert!(price_data.answer.ge(&0f64));
+        );
+        let provider = Provider::new(failover.unwrap());
+
+        let chainlink_contract = eth_contract(&provider).await;
+        let latest = chainlink_contract.latest_round_data().await.unwrap();
+        let historical = chainlink_contract
+            .round_data_at(latest.round_id - 1)
+            .await
+            .unwrap();
+        println!("Received data: {:#?}", historical);
+        assert_eq!(historical.round_id, latest.round_id - 1);
+        assert!(historical.updated_at.le(&latest.updated_at));
     }
 }
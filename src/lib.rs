@@ -2,17 +2,38 @@
 /// This library provides a simple interface to fetch price data from the Chainlink decentralized data feed.
 /// Core is the main module that contains the main struct `Rustlink` that you will need to interact with.
 pub mod core;
+
+mod builder;
+#[cfg(feature = "catalog")]
+mod catalog;
+mod channel;
+mod emission;
+mod ens;
 mod error;
+mod event;
+mod failover;
 mod fetcher;
+mod history;
 mod interface;
+mod multicall;
+mod price;
+mod quorum;
+mod registry;
+mod retry;
+mod store;
+#[cfg(test)]
+mod test_utils;
+mod websocket;
 #[cfg(test)]
 mod tests {
 
     use async_std::channel::unbounded;
+    use ethers::types::I256;
 
-    use crate::core::{Reflector, Rustlink};
+    use crate::core::{Error, Price, Reflector, Rustlink};
 
     #[tokio::test]
+    #[allow(clippy::vec_init_then_push)]
     async fn ensure_price_is_received() {
         let mut contracts: Vec<(String, String)> = Vec::new();
         contracts.push((
@@ -32,8 +53,55 @@ mod tests {
         .unwrap();
 
         rustlink.start();
-        let round_data = receiver.recv().await.unwrap();
-        println!("Received data: {:#?}", round_data);
-        assert!(round_data.answer.ge(&0f64));
+        let event = receiver.recv().await.unwrap();
+        println!("Received data: {:#?}", event);
+        let round_data = event.round().expect("Failed fetching the price");
+        assert!(round_data.price() > Price::new(I256::zero(), 0));
+    }
+
+    #[tokio::test]
+    async fn invalid_construction_rejected() {
+        let contract =
+            |identifier: &str, address: &str| (identifier.to_string(), address.to_string());
+        let eth = contract("ETH", "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e");
+        let try_new = |rpc_url: &str, fetch_interval_seconds, contracts| {
+            let (sender, _receiver) = unbounded();
+            Rustlink::try_new(
+                rpc_url,
+                fetch_interval_seconds,
+                Reflector::Sender(sender),
+                contracts,
+                std::time::Duration::from_secs(3),
+            )
+        };
+
+        assert!(matches!(
+            try_new("not a url", 1, vec![eth.clone()]),
+            Err(Error::InvalidRpcUrl { .. })
+        ));
+        assert!(matches!(
+            try_new(
+                "https://bsc-dataseed1.binance.org/",
+                1,
+                vec![contract("ETH", "0x9ef1")]
+            ),
+            Err(Error::InvalidAddress { .. })
+        ));
+        assert_eq!(
+            try_new(
+                "https://bsc-dataseed1.binance.org/",
+                1,
+                vec![eth.clone(), eth.clone()]
+            )
+            .err(),
+            Some(Error::DuplicateIdentifier("ETH".to_string()))
+        );
+        assert_eq!(
+            try_new("https://bsc-dataseed1.binance.org/", 0, vec![eth.clone()]).err(),
+            Some(Error::ZeroInterval)
+        );
+
+        let rustlink = try_new("https://bsc-dataseed1.binance.org/", 1, vec![eth]).unwrap();
+        assert_eq!(rustlink.stop().await, Err(Error::NotRunning));
     }
 }
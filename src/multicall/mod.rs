@@ -0,0 +1,118 @@
+use ethers::{
+    contract::{Multicall, MulticallVersion},
+    providers::Provider,
+    types::Address,
+};
+
+use crate::core::{Feed, Rustlink};
+use crate::ens::feed_address;
+use crate::failover::Failover;
+use crate::fetcher::{feed_metadata, refresh_on_new_phase};
+use crate::interface::{ChainlinkContract, ContractCallError, Round};
+use crate::retry::retry;
+
+/// Type alias for the round of a feed within a batch, tagged with the identifier of the feed
+pub type BatchedRound<'a> = (
+    &'a str,
+    Result<Round, ContractCallError<&'a Provider<Failover>>>,
+);
+
+/// Retrieves the latest round of the given feeds with a single `aggregate3`
+/// call to a Multicall3 contract.
+///
+/// Every call is allowed to fail on its own, so a failing feed does not affect the
+/// other ones. An error is only returned if the multicall itself fails, it is retried
+/// according to the retry policy of `rustlink`.
+pub async fn fetch_latest_rounds<'a>(
+    rustlink: &'a Rustlink,
+    multicall_address: Address,
+    feeds: &[&'a Feed],
+) -> Result<Vec<BatchedRound<'a>>, ContractCallError<&'a Provider<Failover>>> {
+    let mut rounds = Vec::new();
+    let mut contracts = Vec::new();
+    let mut multicall = Multicall::new_with_chain_id(
+        &rustlink.configuration.provider,
+        Some(multicall_address),
+        None::<u64>,
+    )?
+    .version(MulticallVersion::Multicall3);
+
+    for feed in feeds {
+        // The decimals are needed to decode the answer, skip feeds without metadata.
+        let metadata = match feed_address(rustlink, feed).await {
+            Ok(address) => feed_metadata(rustlink, &feed.identifier, address)
+                .await
+                .map(|metadata| (address, metadata)),
+            Err(error) => Err(error),
+        };
+        let (address, metadata) = match metadata {
+            Ok(metadata) => metadata,
+            Err(error) => {
+                rounds.push((feed.identifier.as_str(), Err(error)));
+                continue;
+            }
+        };
+        let contract = ChainlinkContract::with_decimals(
+            &rustlink.configuration.provider,
+            &feed.identifier,
+            address,
+            metadata.decimals,
+            rustlink.configuration.call_timeout,
+        );
+        multicall.add_call(contract.latest_round_data_call()?, true);
+        contracts.push((contract, address, metadata));
+    }
+
+    if contracts.is_empty() {
+        return Ok(rounds);
+    }
+
+    // Retry the batch, but timeout after the configured call timeout
+    let (attempts, results) = retry(
+        &rustlink.configuration.retry,
+        rustlink.configuration.call_timeout,
+        ContractCallError::is_transient,
+        || async { Ok(multicall.call_raw().await?) },
+    )
+    .await;
+    let results = results.map_err(|error| error.with_attempts(attempts))?;
+    for ((contract, address, metadata), result) in contracts.into_iter().zip(results) {
+        let round = match result {
+            Ok(token) => match contract.decode_round(token) {
+                Ok(round) => {
+                    let round = Round { attempts, ..round };
+                    refresh_on_new_phase(rustlink, contract.identifier, address, &metadata, round)
+                        .await
+                }
+                Err(error) => Err(error.into()),
+            },
+            Err(revert) => Err(ContractCallError::Revert(revert)),
+        };
+        rounds.push((contract.identifier, round));
+    }
+    Ok(rounds)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::core::MULTICALL3_ADDRESS;
+    use crate::multicall::fetch_latest_rounds;
+    use crate::test_utils::{contract, rustlink, ETH, INCH};
+
+    #[tokio::test]
+    async fn valid_batched_answers() {
+        let (rustlink, _receiver) = rustlink(vec![contract(ETH), contract(INCH)]);
+        let rustlink = rustlink.with_multicall(MULTICALL3_ADDRESS);
+
+        let feeds: Vec<_> = rustlink.configuration.contracts.iter().collect();
+        let rounds = fetch_latest_rounds(&rustlink, MULTICALL3_ADDRESS, &feeds)
+            .await
+            .unwrap();
+        println!("Received data: {:#?}", rounds);
+        assert_eq!(rounds.len(), 2);
+        for (identifier, round) in rounds {
+            assert_eq!(round.unwrap().identifier, identifier);
+        }
+    }
+}
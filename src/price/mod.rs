@@ -0,0 +1,236 @@
+use ethers::types::{I256, U256};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{cmp::Ordering, fmt, str::FromStr};
+use thiserror::Error;
+
+/// An exact fixed-point price as reported by a Chainlink feed.
+///
+/// The price is stored as the raw integer answer of the contract together with the number
+/// of decimals of the feed, so `value = 123456789` with `decimals = 8` is `1.23456789`.
+/// Answers are signed 256-bit integers on chain, so prices may be negative.
+/// No floating point arithmetic is involved in any of the conversions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Price {
+    value: I256,
+    decimals: u8,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParsePriceError {
+    #[error("Price is empty")]
+    Empty,
+    #[error("Price contains an invalid character")]
+    InvalidCharacter,
+    #[error("Price does not fit into 256 bits")]
+    Overflow,
+}
+
+impl Price {
+    /// Creates a price from a raw integer and the number of decimals it is scaled by
+    pub fn new(value: I256, decimals: u8) -> Self {
+        Price { value, decimals }
+    }
+
+    /// The raw integer value, scaled by `10^decimals`
+    pub fn value(self) -> I256 {
+        self.value
+    }
+
+    /// Number of decimals the raw value is scaled by
+    pub fn decimals(self) -> u8 {
+        self.decimals
+    }
+
+    /// The integer mantissa of this price, as in `rust_decimal::Decimal::mantissa`.
+    ///
+    /// Together with `scale` this lets you build a decimal without going through a float:
+    /// `Decimal::from_i128_with_scale(price.mantissa(), price.scale())`.
+    /// Returns `None` if the value does not fit into an `i128`.
+    pub fn mantissa(self) -> Option<i128> {
+        i128::try_from(self.value).ok()
+    }
+
+    /// The number of decimal places of this price, as in `rust_decimal::Decimal::scale`
+    pub fn scale(self) -> u32 {
+        self.decimals.into()
+    }
+
+    /// Converts this price into an integer scaled by `10^decimals`.
+    ///
+    /// Additional decimals are truncated towards zero when scaling down. Returns `None` on overflow.
+    pub fn to_scaled(self, decimals: u8) -> Option<I256> {
+        let ten = I256::from(10);
+        match decimals.cmp(&self.decimals) {
+            Ordering::Equal => Some(self.value),
+            Ordering::Greater => self
+                .value
+                .checked_mul(ten.checked_pow((decimals - self.decimals).into())?),
+            Ordering::Less => Some(
+                ten.checked_pow((self.decimals - decimals).into())
+                    .map_or(I256::zero(), |divisor| self.value / divisor),
+            ),
+        }
+    }
+
+    /// Converts this price to the given number of decimals, see `to_scaled`
+    pub fn rescale(self, decimals: u8) -> Option<Price> {
+        Some(Price::new(self.to_scaled(decimals)?, decimals))
+    }
+}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Price {
+    /// Compares the prices by their numeric value, regardless of their decimals
+    fn cmp(&self, other: &Self) -> Ordering {
+        let decimals = self.decimals.max(other.decimals);
+        match (self.to_scaled(decimals), other.to_scaled(decimals)) {
+            (Some(left), Some(right)) => left.cmp(&right),
+            // Only the price with fewer decimals can overflow when scaling up
+            (None, _) if self.value.is_negative() => Ordering::Less,
+            (None, _) => Ordering::Greater,
+            (_, None) if other.value.is_negative() => Ordering::Greater,
+            (_, None) => Ordering::Less,
+        }
+        .then(self.decimals.cmp(&other.decimals))
+    }
+}
+
+impl fmt::Display for Price {
+    /// Formats the price as an exact decimal string, e.g. `3012.45000000` or `-0.00125000`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.value.is_negative() { "-" } else { "" };
+        let digits = format!(
+            "{:0>width$}",
+            self.value.unsigned_abs(),
+            width = self.decimals as usize + 1
+        );
+        let (integer, fraction) = digits.split_at(digits.len() - self.decimals as usize);
+        if fraction.is_empty() {
+            write!(f, "{}{}", sign, integer)
+        } else {
+            write!(f, "{}{}.{}", sign, integer, fraction)
+        }
+    }
+}
+
+impl FromStr for Price {
+    type Err = ParsePriceError;
+
+    /// Parses an exact decimal string. The number of digits after the
+    /// decimal point becomes the number of decimals of the price.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(ParsePriceError::Empty);
+        }
+        let decimals = u8::try_from(fraction.len()).map_err(|_| ParsePriceError::Overflow)?;
+
+        let mut value = U256::zero();
+        for character in integer.chars().chain(fraction.chars()) {
+            let digit = character
+                .to_digit(10)
+                .ok_or(ParsePriceError::InvalidCharacter)?;
+            value = value
+                .checked_mul(10.into())
+                .and_then(|value| value.checked_add(digit.into()))
+                .ok_or(ParsePriceError::Overflow)?;
+        }
+        let value = I256::try_from(value).map_err(|_| ParsePriceError::Overflow)?;
+        Ok(Price::new(if negative { -value } else { value }, decimals))
+    }
+}
+
+/// Prices are serialized as exact decimal strings so they survive JSON round trips
+impl Serialize for Price {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Price {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let price = String::deserialize(deserializer)?;
+        price.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Serializes signed integer answers as decimal strings, since `I256` is
+/// otherwise serialized as its two's complement in hex.
+pub(crate) mod decimal {
+    use ethers::types::I256;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &I256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<I256, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        I256::from_dec_str(&value).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::price::{ParsePriceError, Price};
+    use ethers::types::I256;
+
+    #[test]
+    fn price_string_conversion() {
+        let price = Price::new(I256::from(301245000000i64), 8);
+        assert_eq!(price.to_string(), "3012.45000000");
+        assert_eq!(
+            Price::new(I256::from(5), 18).to_string(),
+            "0.000000000000000005"
+        );
+        assert_eq!(
+            Price::new(I256::from(-125000), 8).to_string(),
+            "-0.00125000"
+        );
+        assert_eq!(Price::new(I256::from(42), 0).to_string(), "42");
+
+        assert_eq!("3012.45000000".parse::<Price>(), Ok(price));
+        assert_eq!(".5".parse::<Price>(), Ok(Price::new(I256::from(5), 1)));
+        assert_eq!(
+            "-0.00125000".parse::<Price>(),
+            Ok(Price::new(I256::from(-125000), 8))
+        );
+        assert_eq!("".parse::<Price>(), Err(ParsePriceError::Empty));
+        assert_eq!(
+            "1,5".parse::<Price>(),
+            Err(ParsePriceError::InvalidCharacter)
+        );
+    }
+
+    #[test]
+    fn price_scaling() {
+        let price = Price::new(I256::from(301245000000i64), 8);
+        assert_eq!(price.to_scaled(2), Some(I256::from(301245)));
+        assert_eq!(
+            price.to_scaled(18),
+            Some(I256::from(3012450000000000000000i128))
+        );
+        assert_eq!(price.to_scaled(80), None);
+        assert_eq!(price.mantissa(), Some(301245000000));
+        assert_eq!(price.scale(), 8);
+
+        assert_eq!(
+            Price::new(I256::from(-125000), 8).to_scaled(3),
+            Some(I256::from(-1))
+        );
+
+        assert!(Price::new(I256::from(3012), 0) < price);
+        assert!(Price::new(I256::from(3013), 0) > price);
+        assert!(Price::new(I256::from(-1), 0) < Price::new(I256::zero(), 8));
+    }
+}
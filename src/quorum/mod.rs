@@ -0,0 +1,91 @@
+use ethers::providers::Provider;
+
+use crate::failover::Failover;
+use crate::interface::Round;
+
+/// Independent providers that each have to report the latest round of a feed, of which at
+/// least `threshold` have to agree on its round id and answer before the round is emitted.
+#[derive(Clone, Debug)]
+pub struct Quorum {
+    /// The providers to query, each with its own RPC endpoints
+    pub providers: Vec<Provider<Failover>>,
+    /// Number of providers that have to report the same round
+    pub threshold: usize,
+}
+
+impl Quorum {
+    /// The round reported by at least `threshold` of the given rounds, if any.
+    /// Rounds agree if they have the same round id and answer.
+    pub(crate) fn agreed_round<'a>(&self, rounds: &'a [Round]) -> Option<&'a Round> {
+        rounds.iter().find(|round| {
+            let agreeing = rounds
+                .iter()
+                .filter(|other| other.round_id == round.round_id && other.answer == round.answer)
+                .count();
+            agreeing >= self.threshold
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::core::Error;
+    use crate::quorum::Quorum;
+    use crate::test_utils::{contract, round_answered, rustlink, ETH};
+
+    #[test]
+    fn quorum_agrees_on_round_and_answer() {
+        let quorum = Quorum {
+            providers: Vec::new(),
+            threshold: 2,
+        };
+
+        let rounds = [
+            round_answered("ETH", 1, 300),
+            round_answered("ETH", 2, 301),
+            round_answered("ETH", 2, 301),
+        ];
+        assert_eq!(quorum.agreed_round(&rounds).unwrap().round_id, 2);
+
+        // A lagging provider and a lying provider
+        let rounds = [
+            round_answered("ETH", 1, 300),
+            round_answered("ETH", 2, 301),
+            round_answered("ETH", 2, 999),
+        ];
+        assert!(quorum.agreed_round(&rounds).is_none());
+        assert!(quorum.agreed_round(&rounds[..1]).is_none());
+    }
+
+    #[test]
+    fn threshold_must_be_a_majority() {
+        let (rustlink, _receiver) = rustlink(vec![contract(ETH)]);
+        let rpc_urls = [
+            "https://bsc-dataseed2.binance.org/",
+            "https://bsc-dataseed3.binance.org/",
+            "https://bsc-rpc.publicnode.com/",
+        ];
+
+        // Two of four providers could agree on two different rounds at once.
+        for threshold in [0, 2, 5] {
+            assert_eq!(
+                rustlink.clone().with_quorum(rpc_urls, threshold).err(),
+                Some(Error::InvalidQuorum {
+                    threshold,
+                    providers: 4
+                })
+            );
+        }
+        assert!(rustlink.clone().with_quorum(rpc_urls, 3).is_ok());
+
+        // Without any other provider, the threshold would be met by the only one.
+        assert_eq!(
+            rustlink.with_quorum(Vec::<&str>::new(), 1).err(),
+            Some(Error::InvalidQuorum {
+                threshold: 1,
+                providers: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use async_std::future::timeout;
+use ethers::{
+    abi::{Abi, Detokenize},
+    contract::Contract,
+    providers::Provider,
+    types::{Address, H160},
+};
+use std::{
+    str::FromStr,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use crate::error::Error;
+use crate::failover::Failover;
+use crate::interface::{ContractCallError, FeedMetadata, RawRound, Round};
+use crate::retry::{retry, RetryPolicy};
+
+/// The address of the Chainlink Feed Registry on Ethereum mainnet
+pub const FEED_REGISTRY_ADDRESS: Address = H160([
+    0x47, 0xfb, 0x25, 0x85, 0xd2, 0xc5, 0x6f, 0xe1, 0x88, 0xd0, 0xe6, 0xec, 0x62, 0x8a, 0x38, 0xb7,
+    0x4f, 0xce, 0xee, 0xdf,
+]);
+
+/// The address the Feed Registry denotes an asset without a token address by, e.g. `USD`.
+///
+/// Crypto assets use the address Chainlink reserved for them, and fiat currencies
+/// their ISO 4217 number as address.
+pub fn denomination(symbol: &str) -> Option<Address> {
+    let iso_4217 = match symbol.to_uppercase().as_str() {
+        "ETH" => return Some(Address::repeat_byte(0xee)),
+        "BTC" => return Some(Address::repeat_byte(0xbb)),
+        "USD" => 840,
+        "GBP" => 826,
+        "EUR" => 978,
+        "JPY" => 392,
+        "KRW" => 410,
+        "CNY" => 156,
+        "AUD" => 36,
+        "CAD" => 124,
+        "CHF" => 756,
+        "ARS" => 32,
+        "PHP" => 608,
+        "NZD" => 554,
+        "SGD" => 702,
+        "NGN" => 566,
+        "ZAR" => 710,
+        "RUB" => 643,
+        "INR" => 356,
+        "BRL" => 986,
+        _ => return None,
+    };
+    Some(Address::from_low_u64_be(iso_4217))
+}
+
+/// The address the Feed Registry denotes an asset by, given either
+/// its symbol, see `denomination`, or its token address
+pub fn asset_address(asset: &str) -> Option<Address> {
+    denomination(asset).or_else(|| Address::from_str(asset).ok())
+}
+
+/// The addresses of the assets of a `(base, quote)` pair, see `asset_address`.
+///
+/// Returns `Error::InvalidAddress` if either asset is unknown.
+pub fn pair_addresses(base: &str, quote: &str) -> Result<(Address, Address), Error> {
+    let address = |asset: &str| {
+        asset_address(asset).ok_or_else(|| Error::InvalidAddress {
+            identifier: format!("{}/{}", base, quote),
+            address: asset.to_string(),
+        })
+    };
+    Ok((address(base)?, address(quote)?))
+}
+
+/// A feed that is read through a Chainlink Feed Registry instead of its own proxy
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegistryPair {
+    /// The address of the registry
+    pub registry: Address,
+    /// The address the registry denotes the base asset by
+    pub base: Address,
+    /// The address the registry denotes the quote asset by
+    pub quote: Address,
+}
+
+/// The bundled Feed Registry ABI, parsed only once
+fn abi() -> &'static Abi {
+    static ABI: OnceLock<Abi> = OnceLock::new();
+    ABI.get_or_init(|| serde_json::from_str(include_str!("FeedRegistryInterface.json")).unwrap())
+}
+
+/// A Chainlink Feed Registry, which maps `(base, quote)` pairs of assets to the
+/// feeds that report their price.
+#[derive(Clone)]
+pub struct FeedRegistry<'a> {
+    pub contract: Contract<&'a Provider<Failover>>,
+    pub call_timeout: Duration,
+    pub retry: RetryPolicy,
+}
+
+impl<'a> FeedRegistry<'a> {
+    /// Creates a new instance of the Feed Registry at `address`, usually `FEED_REGISTRY_ADDRESS`.
+    /// No calls are made to the contract.
+    pub fn new(
+        provider: &'a Provider<Failover>,
+        address: Address,
+        call_timeout: Duration,
+    ) -> FeedRegistry<'a> {
+        FeedRegistry {
+            contract: Contract::new(address, abi().clone(), Arc::new(provider)),
+            call_timeout,
+            retry: RetryPolicy::none(),
+        }
+    }
+
+    /// Retries the `latestRoundData` calls according to `retry`.
+    /// All attempts of a call together are still bounded by the call timeout.
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Calls a method of the registry that takes a `(base, quote)` pair.
+    /// The registry reverts if it has no feed for the pair.
+    async fn call<D: Detokenize>(
+        &self,
+        method: &str,
+        base: Address,
+        quote: Address,
+    ) -> Result<D, ContractCallError<&'a Provider<Failover>>> {
+        Ok(timeout(
+            self.call_timeout,
+            self.contract.method::<_, D>(method, (base, quote))?.call(),
+        )
+        .await??)
+    }
+
+    /// Retrieves the address of the aggregator the registry currently reads the given pair from.
+    /// Unlike a proxy, the aggregator is replaced when the registry moves the pair to a new phase.
+    pub async fn feed(
+        &self,
+        base: Address,
+        quote: Address,
+    ) -> Result<Address, ContractCallError<&'a Provider<Failover>>> {
+        self.call("getFeed", base, quote).await
+    }
+
+    /// Retrieves the number of decimals of the answers of the given pair
+    pub async fn decimals(
+        &self,
+        base: Address,
+        quote: Address,
+    ) -> Result<u8, ContractCallError<&'a Provider<Failover>>> {
+        self.call("decimals", base, quote).await
+    }
+
+    /// Retrieves the current phase of the given pair, which is bumped every time the
+    /// registry moves the pair to a new aggregator
+    pub async fn phase_id(
+        &self,
+        base: Address,
+        quote: Address,
+    ) -> Result<u16, ContractCallError<&'a Provider<Failover>>> {
+        self.call("phaseId", base, quote).await
+    }
+
+    /// Retrieves the metadata of the given pair. The registry has neither a description
+    /// nor a version for it.
+    pub async fn metadata(
+        &self,
+        base: Address,
+        quote: Address,
+    ) -> Result<FeedMetadata, ContractCallError<&'a Provider<Failover>>> {
+        Ok(FeedMetadata {
+            decimals: self.decimals(base, quote).await?,
+            description: None,
+            version: None,
+            aggregator: Some(self.feed(base, quote).await?),
+            phase_id: Some(self.phase_id(base, quote).await?),
+        })
+    }
+
+    /// Retrieves the latest round of the given pair straight from the registry,
+    /// reporting it under `identifier` with the given number of decimals
+    pub async fn latest_round(
+        &self,
+        identifier: &str,
+        base: Address,
+        quote: Address,
+        decimals: u8,
+    ) -> Result<Round, ContractCallError<&'a Provider<Failover>>> {
+        let (attempts, round_call) = retry(
+            &self.retry,
+            self.call_timeout,
+            ContractCallError::is_transient,
+            || async {
+                Ok(self
+                    .contract
+                    .method::<_, RawRound>("latestRoundData", (base, quote))?
+                    .call()
+                    .await?)
+            },
+        )
+        .await;
+        let (round_id, answer, started_at, updated_at, answered_in_round) =
+            round_call.map_err(|error| error.with_attempts(attempts))?;
+
+        Ok(Round {
+            identifier: identifier.to_string(),
+            round_id,
+            answered_in_round,
+            started_at,
+            updated_at,
+            answer,
+            decimals,
+            stale: false,
+            attempts,
+        })
+    }
+
+    /// Retrieves the latest round of the given pair straight from the registry,
+    /// reporting it under `identifier`
+    pub async fn latest_round_data(
+        &self,
+        identifier: &str,
+        base: Address,
+        quote: Address,
+    ) -> Result<Round, ContractCallError<&'a Provider<Failover>>> {
+        let decimals = self.decimals(base, quote).await?;
+        self.latest_round(identifier, base, quote, decimals).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use ethers::types::Address;
+    use std::str::FromStr;
+
+    use crate::error::Error;
+    use crate::registry::{asset_address, denomination, pair_addresses, FEED_REGISTRY_ADDRESS};
+
+    #[test]
+    fn assets_resolved_to_registry_addresses() {
+        assert_eq!(
+            denomination("ETH").unwrap(),
+            Address::from_str("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE").unwrap()
+        );
+        assert_eq!(
+            denomination("btc").unwrap(),
+            Address::from_str("0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB").unwrap()
+        );
+        assert_eq!(
+            denomination("USD").unwrap(),
+            Address::from_str("0x0000000000000000000000000000000000000348").unwrap()
+        );
+        assert!(denomination("LINK").is_none());
+
+        let link = "0x514910771AF9Ca656af840dff83E8264EcF986CA";
+        assert_eq!(
+            asset_address(link).unwrap(),
+            Address::from_str(link).unwrap()
+        );
+        assert!(asset_address("LINK").is_none());
+        assert!(matches!(
+            pair_addresses("ETH", "LINK"),
+            Err(Error::InvalidAddress { address, .. }) if address == "LINK"
+        ));
+
+        assert_eq!(
+            FEED_REGISTRY_ADDRESS,
+            Address::from_str("0x47Fb2585D2C56Fe188D0E6ec628a38b74fCeeeDf").unwrap()
+        );
+    }
+}
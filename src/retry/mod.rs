@@ -0,0 +1,175 @@
+use async_std::future::{timeout, TimeoutError};
+use futures::Future;
+use rand::Rng;
+use std::time::Duration;
+use workflow_rs::core::task::sleep;
+
+/// How often a failing contract call is retried, and how long to wait in between.
+///
+/// The delay before retry `n` is `base_delay * 2^(n - 1)`, capped at `max_delay`. With jitter
+/// enabled a random delay between zero and that value is used instead, so instances that
+/// failed at the same time do not all retry at the same time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub base_delay: Duration,
+    /// Upper bound of the delay between two attempts
+    pub max_delay: Duration,
+    /// Whether to randomize the delays
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(2),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Only attempts every call once
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// The delay before retry `retry`, without jitter
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// The delay before retry `retry`
+    fn delay(&self, retry: u32) -> Duration {
+        let backoff = self.backoff(retry);
+        if !self.jitter || backoff.is_zero() {
+            return backoff;
+        }
+        rand::thread_rng().gen_range(Duration::ZERO..=backoff)
+    }
+}
+
+/// Calls `call` until it succeeds, fails with an error that `is_transient` rejects or
+/// `policy.max_attempts` are used up. The whole sequence, including the delays, is bounded
+/// by `call_timeout`.
+///
+/// Returns the result of the last attempt together with the number of attempts made.
+pub(crate) async fn retry<F, Fut, T, E>(
+    policy: &RetryPolicy,
+    call_timeout: Duration,
+    is_transient: impl Fn(&E) -> bool,
+    mut call: F,
+) -> (u32, Result<T, E>)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: From<TimeoutError>,
+{
+    let mut attempts = 0;
+    let result = timeout(call_timeout, async {
+        loop {
+            attempts += 1;
+            match call().await {
+                Err(error) if attempts < policy.max_attempts && is_transient(&error) => {
+                    sleep(policy.delay(attempts)).await;
+                }
+                result => return result,
+            }
+        }
+    })
+    .await;
+
+    match result {
+        Ok(result) => (attempts, result),
+        Err(error) => (attempts, Err(error.into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use async_std::future::TimeoutError;
+    use std::time::Duration;
+
+    use crate::retry::{retry, RetryPolicy};
+
+    #[derive(Debug, PartialEq)]
+    enum CallError {
+        Transient,
+        Permanent,
+        Timeout,
+    }
+
+    impl From<TimeoutError> for CallError {
+        fn from(_: TimeoutError) -> Self {
+            CallError::Timeout
+        }
+    }
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(4),
+            jitter: true,
+        }
+    }
+
+    #[test]
+    fn backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy {
+            jitter: false,
+            ..policy(10)
+        };
+        let delays: Vec<u128> = (1..=5)
+            .map(|retry| policy.backoff(retry).as_millis())
+            .collect();
+        assert_eq!(delays, vec![1, 2, 4, 4, 4]);
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(4));
+    }
+
+    #[tokio::test]
+    async fn transient_failures_retried() {
+        let is_transient = |error: &CallError| *error == CallError::Transient;
+        let timeout = Duration::from_secs(1);
+
+        let mut calls = 0;
+        let (attempts, result) = retry(&policy(5), timeout, is_transient, || {
+            calls += 1;
+            let result = if calls < 3 {
+                Err(CallError::Transient)
+            } else {
+                Ok(calls)
+            };
+            async move { result }
+        })
+        .await;
+        assert_eq!((attempts, result), (3, Ok(3)));
+
+        let (attempts, result) = retry(&policy(5), timeout, is_transient, || async {
+            Err::<(), _>(CallError::Permanent)
+        })
+        .await;
+        assert_eq!((attempts, result), (1, Err(CallError::Permanent)));
+
+        let (attempts, result) = retry(&policy(2), timeout, is_transient, || async {
+            Err::<(), _>(CallError::Transient)
+        })
+        .await;
+        assert_eq!((attempts, result), (2, Err(CallError::Transient)));
+
+        let (attempts, result) = retry(&policy(5), Duration::ZERO, is_transient, || {
+            futures::future::pending::<Result<(), CallError>>()
+        })
+        .await;
+        assert_eq!((attempts, result), (1, Err(CallError::Timeout)));
+    }
+}
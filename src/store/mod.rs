@@ -0,0 +1,123 @@
+use async_std::sync::RwLock;
+use std::{collections::HashMap, sync::Arc};
+use workflow_rs::core::time::unixtime_as_millis_u64;
+
+use crate::interface::Round;
+
+/// The latest round received for a feed
+#[derive(Clone, Debug)]
+struct Entry {
+    round: Round,
+    /// Unix timestamp in milliseconds of when the round was received
+    received_at: u64,
+}
+
+/// In-memory store of the latest round of every feed, by identifier.
+/// Clones share the same underlying store.
+#[derive(Clone, Default)]
+pub struct Store {
+    entries: Arc<RwLock<HashMap<String, Entry>>>,
+}
+
+/// Whether `round` was reported before the `latest` round of the same feed
+fn is_older(round: &Round, latest: &Round) -> bool {
+    round.round_id < latest.round_id || round.updated_at < latest.updated_at
+}
+
+impl Store {
+    /// Stores a round as the latest one of its feed, unless a newer round is stored already.
+    /// Returns whether the round was stored.
+    pub async fn update(&self, round: Round) -> bool {
+        let mut entries = self.entries.write().await;
+        if let Some(entry) = entries.get(&round.identifier) {
+            if is_older(&round, &entry.round) {
+                return false;
+            }
+        }
+        let entry = Entry {
+            round,
+            received_at: unixtime_as_millis_u64(),
+        };
+        entries.insert(entry.round.identifier.clone(), entry);
+        true
+    }
+
+    /// Whether a newer round than `round` is stored for its feed already
+    pub async fn is_outdated(&self, round: &Round) -> bool {
+        let entries = self.entries.read().await;
+        entries
+            .get(&round.identifier)
+            .is_some_and(|entry| is_older(round, &entry.round))
+    }
+
+    /// Marks `round` as stale, if it is still the latest round of its feed
+    pub async fn mark_stale(&self, round: &Round) {
+        let mut entries = self.entries.write().await;
+        if let Some(entry) = entries.get_mut(&round.identifier) {
+            if entry.round.round_id == round.round_id {
+                entry.round.stale = true;
+            }
+        }
+    }
+
+    /// The latest round of a feed
+    pub async fn latest(&self, identifier: &str) -> Option<Round> {
+        let entries = self.entries.read().await;
+        entries.get(identifier).map(|entry| entry.round.clone())
+    }
+
+    /// The latest round of every feed
+    pub async fn all_latest(&self) -> HashMap<String, Round> {
+        let entries = self.entries.read().await;
+        entries
+            .iter()
+            .map(|(identifier, entry)| (identifier.clone(), entry.round.clone()))
+            .collect()
+    }
+
+    /// Unix timestamp in milliseconds of when the latest round of a feed was received
+    pub async fn last_update(&self, identifier: &str) -> Option<u64> {
+        let entries = self.entries.read().await;
+        entries.get(identifier).map(|entry| entry.received_at)
+    }
+
+    /// Unix timestamps in milliseconds of when the latest round of every feed was received
+    pub async fn last_updates(&self) -> HashMap<String, u64> {
+        let entries = self.entries.read().await;
+        entries
+            .iter()
+            .map(|(identifier, entry)| (identifier.clone(), entry.received_at))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::store::Store;
+    use crate::test_utils::round;
+
+    #[tokio::test]
+    async fn latest_round_per_identifier() {
+        let store = Store::default();
+        assert!(store.latest("ETH").await.is_none());
+
+        store.update(round("ETH", 1)).await;
+        store.update(round("ETH", 2)).await;
+        store.update(round("BTC", 7)).await;
+
+        // A fetch that finished late does not replace the newer round.
+        assert!(store.is_outdated(&round("ETH", 1)).await);
+        assert!(!store.update(round("ETH", 1)).await);
+        assert!(store.update(round("ETH", 2)).await);
+
+        assert_eq!(store.latest("ETH").await.unwrap().round_id, 2);
+        store.mark_stale(&round("ETH", 1)).await;
+        assert!(!store.latest("ETH").await.unwrap().stale);
+        store.mark_stale(&round("ETH", 2)).await;
+        assert!(store.latest("ETH").await.unwrap().stale);
+        assert_eq!(store.all_latest().await.len(), 2);
+        assert!(store.last_update("BTC").await.unwrap() > 0);
+        assert!(store.last_update("1INCH").await.is_none());
+    }
+}
@@ -0,0 +1,120 @@
+use async_std::channel::{unbounded, Receiver};
+use ethers::types::{I256, U256};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+use crate::core::{ContractEntry, Event, Reflector, Round, Rustlink};
+
+/// The ETH/USD feed on BSC
+pub const ETH: (&str, &str) = ("ETH", "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e");
+
+/// The 1INCH/USD feed on BSC
+pub const INCH: (&str, &str) = ("1INCH", "0x9a177Bb9f5b6083E962f9e62bD21d4b5660Aeb03");
+
+/// Converts an identifier and address pair into a contract entry
+pub fn contract((identifier, address): (&str, &str)) -> (String, String) {
+    (identifier.to_string(), address.to_string())
+}
+
+/// A round of 3012.45 with 8 decimals, answered in its own round
+pub fn round(identifier: &str, round_id: u128) -> Round {
+    round_answered(identifier, round_id, 301245000000)
+}
+
+/// A round of the given answer with 8 decimals, answered in its own round
+pub fn round_answered(identifier: &str, round_id: u128, answer: i64) -> Round {
+    Round {
+        identifier: identifier.to_string(),
+        round_id,
+        answered_in_round: round_id,
+        started_at: U256::zero(),
+        updated_at: U256::zero(),
+        answer: I256::from(answer),
+        decimals: 8,
+        stale: false,
+        attempts: 1,
+    }
+}
+
+/// A rustlink instance fetching the contracts every second from BSC,
+/// along with the receiver of its events
+pub fn rustlink<C>(contracts: Vec<C>) -> (Rustlink, Receiver<Event>)
+where
+    C: Into<ContractEntry>,
+{
+    rustlink_with("https://bsc-dataseed1.binance.org/", contracts)
+}
+
+/// A rustlink instance fetching the contracts every second from the given rpc url,
+/// along with the receiver of its events
+pub fn rustlink_with<C>(rpc_url: &str, contracts: Vec<C>) -> (Rustlink, Receiver<Event>)
+where
+    C: Into<ContractEntry>,
+{
+    let (sender, receiver) = unbounded();
+    let rustlink = Rustlink::try_new(
+        rpc_url,
+        1,
+        Reflector::Sender(sender),
+        contracts,
+        std::time::Duration::from_secs(10),
+    )
+    .unwrap();
+    (rustlink, receiver)
+}
+
+/// Serves JSON-RPC requests, returns its url.
+/// `respond` receives the method and the first parameter of each request and returns
+/// its hex encoded result, requests it has no result for are reverted.
+pub async fn serve<F>(respond: F) -> String
+where
+    F: Fn(&str, &serde_json::Value) -> Option<String> + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut request = Vec::new();
+            let mut buffer = [0; 4096];
+            // Read until the whole JSON body has arrived.
+            while !request.ends_with(b"}") {
+                match stream.read(&mut buffer).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => request.extend_from_slice(&buffer[..read]),
+                }
+            }
+            let request = String::from_utf8_lossy(&request);
+            let body = request.split("\r\n\r\n").nth(1).unwrap_or_default();
+            let body: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+            let method = body["method"].as_str().unwrap_or_default();
+            let result = respond(method, &body["params"][0])
+                .map(|result| format!(r#""result":"{}""#, result))
+                .unwrap_or_else(|| {
+                    r#""error":{"code":3,"message":"execution reverted"}"#.to_string()
+                });
+            let body = format!(r#"{{"jsonrpc":"2.0","id":{},{}}}"#, body["id"], result);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+    url
+}
+
+/// Accepts connections without ever answering them, returns its url
+pub async fn hang() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let mut streams = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            streams.push(stream);
+        }
+    });
+    url
+}
@@ -0,0 +1,174 @@
+use ethers::{
+    providers::{Middleware, Provider, ProviderError, Ws},
+    types::{Address, Filter, U64},
+};
+use futures::{stream, StreamExt};
+use std::{collections::HashMap, time::Duration};
+use workflow_rs::core::task::sleep;
+
+use crate::core::{Feed, Rustlink};
+use crate::fetcher::fetch_feed;
+
+/// How long to wait before reconnecting after the websocket connection was lost
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// The feeds to refresh when a contract emits a log, by contract address.
+///
+/// Every feed is refreshed when its proxy or the aggregator it currently points to emits a log.
+/// Feeds whose metadata has not been loaded yet are only followed through their proxy,
+/// and feeds whose ENS name has not been resolved yet are not followed at all. Feeds read
+/// through a Feed Registry have no proxy, they are only followed through their aggregator.
+pub async fn feeds_by_address(rustlink: &Rustlink) -> HashMap<Address, Vec<&Feed>> {
+    let metadata = rustlink.feed_metadata.read().await;
+    let ens_addresses = rustlink.ens_addresses.read().await;
+    let mut feeds: HashMap<Address, Vec<&Feed>> = HashMap::new();
+    for feed in &rustlink.configuration.contracts {
+        let address = match (&feed.ens_name, feed.registry) {
+            (Some(_), _) => ens_addresses.get(&feed.identifier).copied(),
+            // A registry does not log the rounds it forwards, only the aggregator does.
+            (None, Some(_)) => None,
+            (None, None) => Some(feed.address),
+        };
+        if let Some(address) = address {
+            feeds.entry(address).or_default().push(feed);
+        }
+        let aggregator = metadata
+            .get(&feed.identifier)
+            .and_then(|metadata| metadata.aggregator);
+        if let Some(aggregator) = aggregator {
+            feeds.entry(aggregator).or_default().push(feed);
+        }
+    }
+    feeds
+}
+
+/// Refreshes the feeds whenever their contracts emit a log, as announced through
+/// the websocket at `ws_url`. Runs indefinitely, reconnecting when the connection is lost,
+/// unless there are no feeds to follow.
+pub async fn follow_logs(rustlink: &Rustlink, ws_url: &str) {
+    if rustlink.configuration.contracts.is_empty() {
+        return;
+    }
+    loop {
+        // A filter without addresses matches the logs of every contract on the chain,
+        // so wait until the address of a feed is known, e.g. once its ENS name is resolved.
+        if feeds_by_address(rustlink).await.is_empty() {
+            log::debug!("No feed address is known yet, waiting before subscribing");
+            sleep(RECONNECT_DELAY).await;
+            continue;
+        }
+        match Provider::<Ws>::connect(ws_url).await {
+            Ok(provider) => {
+                if let Err(error) = follow_logs_with(rustlink, &provider).await {
+                    log::error!("Lost subscription to {}: {}", ws_url, error);
+                }
+            }
+            Err(error) => log::error!("Failed connecting to {}: {}", ws_url, error),
+        }
+        sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// Subscribes to the logs of every feed and refreshes the feeds that emitted one.
+/// Returns once the subscription ends, or once a feed has moved to a new aggregator
+/// so the subscription has to be renewed.
+async fn follow_logs_with(
+    rustlink: &Rustlink,
+    provider: &Provider<Ws>,
+) -> Result<(), ProviderError> {
+    let feeds = feeds_by_address(rustlink).await;
+    let filter = Filter::new().address(feeds.keys().copied().collect::<Vec<_>>());
+    let mut logs = provider.subscribe_logs(&filter).await?;
+
+    // Rounds may have been reported while there was no subscription.
+    refresh(rustlink, rustlink.configuration.contracts.iter()).await;
+    if is_outdated(rustlink, &feeds).await {
+        return Ok(());
+    }
+
+    // A round usually emits several logs within the same block, it only needs one refresh.
+    let mut refreshed_in: HashMap<Address, U64> = HashMap::new();
+    while let Some(log) = logs.next().await {
+        let Some(block_number) = log.block_number else {
+            continue;
+        };
+        if refreshed_in.insert(log.address, block_number) == Some(block_number) {
+            continue;
+        }
+        // Logs arrive in block order, so the earlier blocks will not be seen again.
+        refreshed_in.retain(|_, refreshed| *refreshed >= block_number);
+        if let Some(feeds) = feeds.get(&log.address) {
+            refresh(rustlink, feeds.iter().copied()).await;
+        }
+        if is_outdated(rustlink, &feeds).await {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+/// Refreshes the given feeds, with at most `max_concurrent_fetches` fetches in flight at once
+async fn refresh<'a>(rustlink: &'a Rustlink, feeds: impl Iterator<Item = &'a Feed>) {
+    stream::iter(feeds)
+        .for_each_concurrent(rustlink.configuration.max_concurrent_fetches, |feed| {
+            fetch_feed(rustlink, feed)
+        })
+        .await;
+}
+
+/// Whether the subscription misses an address that feeds have to be followed through by now.
+/// Refreshing a feed loads its metadata, or reloads it when its proxy has moved to a new aggregator.
+async fn is_outdated(rustlink: &Rustlink, feeds: &HashMap<Address, Vec<&Feed>>) -> bool {
+    let current = feeds_by_address(rustlink).await;
+    let outdated = current.keys().any(|address| !feeds.contains_key(address));
+    if outdated {
+        log::info!("A feed is followed through a new address, renewing the subscription");
+    }
+    outdated
+}
+
+#[cfg(test)]
+mod tests {
+
+    use ethers::types::{Address, U256};
+
+    use crate::core::FeedMetadata;
+    use crate::test_utils::{contract, rustlink, ETH, INCH};
+    use crate::websocket::{feeds_by_address, is_outdated};
+
+    #[tokio::test]
+    async fn feeds_followed_through_proxy_and_aggregator() {
+        let (rustlink, _receiver) = rustlink(vec![contract(ETH), contract(INCH)]);
+        let rustlink = rustlink
+            .with_websocket("wss://bsc-rpc.publicnode.com")
+            .unwrap();
+
+        // Subscribed before the metadata was loaded, the feeds are only followed through their proxies.
+        let subscribed = feeds_by_address(&rustlink).await;
+        assert_eq!(subscribed.len(), 2);
+
+        let aggregator = Address::repeat_byte(0x11);
+        rustlink.feed_metadata.write().await.insert(
+            "ETH".to_string(),
+            FeedMetadata {
+                decimals: 8,
+                description: Some("ETH / USD".to_string()),
+                version: Some(U256::from(4)),
+                aggregator: Some(aggregator),
+                phase_id: Some(1),
+            },
+        );
+
+        assert!(is_outdated(&rustlink, &subscribed).await);
+        let feeds = feeds_by_address(&rustlink).await;
+        assert!(!is_outdated(&rustlink, &feeds).await);
+        assert_eq!(feeds.len(), 3);
+        assert_eq!(feeds[&aggregator][0].identifier, "ETH");
+        let proxy = rustlink.configuration.contracts[1].address;
+        assert_eq!(feeds[&proxy][0].identifier, "1INCH");
+
+        assert!(rustlink
+            .with_websocket("https://bsc-rpc.publicnode.com")
+            .is_err());
+    }
+}